@@ -0,0 +1,70 @@
+//! Compare looking up students by login in a list and in a map
+//!
+//! Run with `cargo bench --bench student_lookup`.
+
+use epitok::student::{Presence, Student};
+use std::collections::HashMap;
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+/// Number of students registered to an event, such as a big amphitheater
+const STUDENTS: usize = 300;
+
+/// Number of times every student is looked up
+const ROUNDS: usize = 1000;
+
+fn login(i: usize) -> String {
+    format!("student.{}@epitech.eu", i)
+}
+
+fn measure(name: &str, mut lookup: impl FnMut(&str) -> bool) -> Duration {
+    let logins: Vec<String> = (0..STUDENTS).map(login).collect();
+
+    let start = Instant::now();
+    for _ in 0..ROUNDS {
+        for login in &logins {
+            assert!(lookup(black_box(login)));
+        }
+    }
+    let elapsed = start.elapsed();
+
+    println!(
+        "{:<8} {:>10.1?} per lookup",
+        name,
+        elapsed / (STUDENTS * ROUNDS) as u32
+    );
+    elapsed
+}
+
+fn main() {
+    let students: Vec<Student> = (0..STUDENTS)
+        .map(|i| {
+            Student::builder()
+                .login(&login(i))
+                .presence(Presence::Present)
+                .build()
+        })
+        .collect();
+
+    let map: HashMap<String, Student> = students
+        .iter()
+        .map(|student| {
+            let copy = Student::builder()
+                .login(student.get_login())
+                .presence(*student.get_presence())
+                .build();
+            (student.get_login().to_string(), copy)
+        })
+        .collect();
+
+    let vec = measure("Vec", |login| {
+        students.iter().any(|student| student.get_login() == login)
+    });
+    let hash = measure("HashMap", |login| map.contains_key(login));
+
+    println!(
+        "HashMap is {:.1}x faster with {} students",
+        vec.as_secs_f64() / hash.as_secs_f64(),
+        STUDENTS
+    );
+}
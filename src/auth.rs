@@ -6,6 +6,7 @@
 //!
 //! ```no_run
 //! use epitok::auth::Auth;
+//! use epitok::intra::redact_autologin;
 //!
 //! # #[async_std::main]
 //! # async fn main() -> Result<(), Box<dyn std::error::Error>> {
@@ -13,18 +14,36 @@
 //! let mut user = Auth::new();
 //! user.sign_in(autologin).await?;
 //!
-//! println!("autologin : {}", user.autologin().as_ref().unwrap());
+//! println!("autologin : {}", redact_autologin(user.autologin().as_ref().unwrap()));
 //! println!("login     : {}", user.login().as_ref().unwrap());
 //! println!("name      : {}", user.name().as_ref().unwrap());
 //! # Ok(())
 //! # }
 //! ```
 
+use crate::event::{list_events_today, Event};
 use crate::intra;
+use chrono::{DateTime, Local};
+use regex::Regex;
+use std::sync::OnceLock;
 use std::{error, fmt};
+use zeroize::{Zeroize, Zeroizing};
 
 #[derive(Debug)]
 /// Error possibilities
+///
+/// Errors coming from the intranet are wrapped, and can be retrieved with `source`
+///
+/// ```
+/// use epitok::auth::Error;
+/// use std::error::Error as _;
+///
+/// # fn example(error: &Error) {
+/// if let Some(source) = error.source() {
+///     eprintln!("intranet error: {}", source);
+/// }
+/// # }
+/// ```
 pub enum Error {
     /// Intra error
     IntraError(intra::Error),
@@ -34,43 +53,50 @@ pub enum Error {
     NoLogin,
     /// There is no name associated with the account, should not be possible though
     NoName,
+    /// User is not signed in
+    NotSignedIn,
 }
 
-impl error::Error for Error {}
+impl error::Error for Error {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            Error::IntraError(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<intra::Error> for Error {
+    fn from(e: intra::Error) -> Self {
+        Error::IntraError(e)
+    }
+}
 
 impl fmt::Display for Error {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        let message: String = match *self {
-            Error::IntraError(intra::Error::Network) => intra::Error::Network.to_string(),
-            Error::IntraError(intra::Error::AccessDenied) => intra::Error::AccessDenied.to_string(),
-            Error::IntraError(intra::Error::NotFound) => intra::Error::NotFound.to_string(),
-            Error::IntraError(intra::Error::IntraDown) => intra::Error::IntraDown.to_string(),
-            Error::IntraError(intra::Error::Parsing) => intra::Error::Parsing.to_string(),
-            Error::IntraError(intra::Error::Empty) => intra::Error::Empty.to_string(),
+        let message: String = match self {
+            Error::IntraError(e) => e.to_string(),
             Error::Credentials => "Invalid autologin link provided".into(),
             Error::NoLogin => "No login associated with intranet profile".into(),
             Error::NoName => "No name associated with intranet profile".into(),
+            Error::NotSignedIn => "You are not signed in to the intranet".into(),
         };
         write!(f, "{}", message)
     }
 }
 
 /// Authentication status
+#[derive(Debug, Default)]
 pub enum Status {
     /// Signed in
     SignedIn,
     /// Signed out
+    #[default]
     SignedOut,
     /// Could not sign in
     Error(Error),
 }
 
-impl Default for Status {
-    fn default() -> Self {
-        Status::SignedOut
-    }
-}
-
 /// # Authentication
 ///
 /// Authentication and identity verification to the intranet
@@ -84,8 +110,48 @@ pub struct Auth {
     login: Option<String>,
     /// User's name
     name: Option<String>,
+    /// User's profile picture URL
+    picture: Option<String>,
+    /// User's intranet id
+    id: Option<u64>,
+    /// User's internal email address
+    internal_email: Option<String>,
     /// Status
     status: Status,
+    /// When the last successful sign-in happened
+    signed_in_at: Option<DateTime<Local>>,
+}
+
+/// The autologin link is overwritten in memory when the struct is dropped
+impl Drop for Auth {
+    fn drop(&mut self) {
+        self.clear_autologin();
+    }
+}
+
+/// The autologin link is redacted, to avoid leaking it in logs
+///
+/// ```
+/// use epitok::auth::Auth;
+///
+/// println!("{:?}", Auth::new());
+/// ```
+impl fmt::Debug for Auth {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Auth")
+            .field(
+                "autologin",
+                &self.autologin.as_deref().map(intra::redact_autologin),
+            )
+            .field("login", &self.login)
+            .field("name", &self.name)
+            .field("picture", &self.picture)
+            .field("id", &self.id)
+            .field("internal_email", &self.internal_email)
+            .field("status", &self.status)
+            .field("signed_in_at", &self.signed_in_at)
+            .finish()
+    }
 }
 
 impl Auth {
@@ -95,22 +161,91 @@ impl Auth {
     }
 
     /// Sign-in with autologin link
+    ///
+    /// The autologin can be provided in three forms:
+    /// - the full link: `https://intra.epitech.eu/auth-abcdefghijklmnopqrstuvwxyz1234567890abcd`
+    /// - the last part of the link: `auth-abcdefghijklmnopqrstuvwxyz1234567890abcd`
+    /// - the token only: `abcdefghijklmnopqrstuvwxyz1234567890abcd`
+    ///
+    /// The full link will be stored in every case.
     pub async fn sign_in(&mut self, autologin: &str) -> Result<(), Box<dyn error::Error>> {
+        self.authenticate(intra::IntraClient::shared(), autologin)
+            .await?;
+        Ok(())
+    }
+
+    /// Create and sign-in with autologin link
+    ///
+    /// The returned user is signed in, with its login and name available.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use epitok::auth::Auth;
+    ///
+    /// # #[async_std::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let autologin = "https://intra.epitech.eu/auth-abcdefghijklmnopqrstuvwxyz1234567890abcd";
+    /// let user = Auth::from_autologin(autologin).await?;
+    ///
+    /// println!("name: {}", user.name().as_ref().unwrap());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn from_autologin(autologin: &str) -> Result<Self, Error> {
+        Self::from_autologin_with_client(intra::IntraClient::shared(), autologin).await
+    }
+
+    /// Create and sign-in with autologin link, with a client instead of the shared one
+    ///
+    /// See `from_autologin`.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use epitok::auth::Auth;
+    /// use epitok::intra::{IntraClient, IntraConfig};
+    ///
+    /// # #[async_std::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let autologin = "https://intra.epitech.eu/auth-abcdefghijklmnopqrstuvwxyz1234567890abcd";
+    /// let client = IntraClient::with_config(IntraConfig::from_env());
+    /// let user = Auth::from_autologin_with_client(&client, autologin).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn from_autologin_with_client(
+        client: &intra::IntraClient,
+        autologin: &str,
+    ) -> Result<Self, Error> {
+        let mut auth = Self::new();
+        auth.authenticate(client, autologin).await?;
+        Ok(auth)
+    }
+
+    async fn authenticate(
+        &mut self,
+        client: &intra::IntraClient,
+        autologin: &str,
+    ) -> Result<(), Error> {
         // Check autologin
-        if !Self::check_autologin(autologin) {
-            self.status = Status::Error(Error::Credentials);
-            return Err(Error::Credentials.into());
-        }
+        let autologin = match Self::check_autologin(autologin) {
+            Some(autologin) => Zeroizing::new(autologin),
+            None => {
+                self.status = Status::Error(Error::Credentials);
+                return Err(Error::Credentials);
+            }
+        };
 
         // Store new autologin
-        self.set_autologin(autologin);
+        self.set_autologin(&autologin);
 
-        let url = format!("{}/user?format=json", autologin);
+        let url = Zeroizing::new(format!("{}/user?format=json", autologin.as_str()));
 
-        let json = match intra::get_obj(&url).await {
+        let json = match client.get_obj(&url).await {
             Ok(intra_request) => intra_request,
             Err(e) => {
-                self.status = Status::Error(Error::IntraError(e));
+                self.status = Status::Error(e.clone().into());
                 return Err(e.into());
             }
         };
@@ -120,7 +255,7 @@ impl Auth {
             Some(login) => login,
             None => {
                 self.status = Status::Error(Error::NoLogin);
-                return Err(Error::NoLogin.into());
+                return Err(Error::NoLogin);
             }
         };
 
@@ -129,23 +264,85 @@ impl Auth {
             Some(name) => name,
             None => {
                 self.status = Status::Error(Error::NoName);
-                return Err(Error::NoName.into());
+                return Err(Error::NoName);
             }
         };
 
         self.set_login(login);
         self.set_name(name);
+        self.set_picture(json["picture"].as_str());
+        self.set_id(&json["id"]);
+        self.internal_email = json["internal_email"].as_str().map(String::from);
         self.status = Status::SignedIn;
+        self.signed_in_at = Some(Local::now());
+
+        Ok(())
+    }
+
+    /// Verify that the stored autologin link is still valid
+    ///
+    /// Login and name are updated if they changed on the intranet.
+    ///
+    /// If the intranet can't be reached, the user stays signed in and the error is returned,
+    /// so applications can show a warning instead of signing the user out.
+    /// The user is signed out only if the intranet denies access with the autologin link.
+    pub async fn refresh(&mut self) -> Result<(), Box<dyn error::Error>> {
+        let url = match self.autologin() {
+            Some(autologin) => Zeroizing::new(format!("{}/user?format=json", autologin)),
+            None => return Err(Error::NotSignedIn.into()),
+        };
+
+        let json = match intra::get_obj(&url).await {
+            Ok(intra_request) => intra_request,
+            Err(intra::Error::AccessDenied) => {
+                self.sign_out();
+                self.status = Status::Error(Error::Credentials);
+                return Err(Error::Credentials.into());
+            }
+            Err(e) => return Err(Error::from(e).into()), // Keep current state, the autologin may be fine
+        };
+
+        if let Some(login) = json["login"].as_str() {
+            self.set_login(login);
+        }
+
+        if let Some(name) = json["title"].as_str() {
+            self.set_name(name);
+        }
+
+        self.set_picture(json["picture"].as_str());
+        self.set_id(&json["id"]);
+        self.internal_email = json["internal_email"].as_str().map(String::from);
+
+        self.status = Status::SignedIn;
+        self.signed_in_at = Some(Local::now());
 
         Ok(())
     }
 
     /// Sign-out
+    ///
+    /// The autologin link is overwritten in memory before being removed.
+    /// Copies of the autologin link made outside of this struct are not cleared.
+    ///
+    /// ```
+    /// use epitok::auth::Auth;
+    ///
+    /// let mut user = Auth::new();
+    /// user.sign_out();
+    ///
+    /// assert!(user.autologin().is_none());
+    /// assert!(!user.is_signed_in());
+    /// ```
     pub fn sign_out(&mut self) {
-        self.autologin = None;
+        self.clear_autologin();
         self.login = None;
         self.name = None;
+        self.picture = None;
+        self.id = None;
+        self.internal_email = None;
         self.status = Status::SignedOut;
+        self.signed_in_at = None;
     }
 
     /// Retrieve autologin link
@@ -154,9 +351,16 @@ impl Auth {
     }
 
     fn set_autologin(&mut self, autologin: &str) {
+        self.clear_autologin();
         self.autologin = Some(autologin.to_string());
     }
 
+    fn clear_autologin(&mut self) {
+        if let Some(mut autologin) = self.autologin.take() {
+            autologin.zeroize();
+        }
+    }
+
     /// Retrieve email address
     pub fn login(&self) -> &Option<String> {
         &self.login
@@ -175,20 +379,194 @@ impl Auth {
         self.name = Some(name.to_string());
     }
 
+    /// Retrieve profile picture URL
+    pub fn picture_url(&self) -> Option<String> {
+        self.picture.clone()
+    }
+
+    fn set_picture(&mut self, picture: Option<&str>) {
+        self.picture = match picture {
+            Some(picture) if picture.starts_with("http") => Some(picture.to_string()),
+            Some(picture) if !picture.is_empty() => Some(format!(
+                "https://intra.epitech.eu/{}",
+                picture.trim_start_matches('/')
+            )),
+            _ => None,
+        };
+    }
+
+    /// Retrieve intranet id
+    pub fn id(&self) -> Option<u64> {
+        self.id
+    }
+
+    fn set_id(&mut self, id: &serde_json::Value) {
+        self.id = intra::parse_value(id);
+    }
+
+    /// Retrieve internal email address
+    pub fn internal_email(&self) -> Option<&str> {
+        self.internal_email.as_deref()
+    }
+
     /// Get current status
     pub fn status(&self) -> &Status {
         &self.status
     }
 
-    fn check_autologin(new: &str) -> bool {
-        // prepare regex
-        let rule = "^(https://intra.epitech.eu/auth-[a-z0-9]{40})$";
-        let re = match regex::Regex::new(rule) {
-            Ok(re) => re,
-            Err(_) => return false,
+    /// Check if the last sign-in attempt succeeded
+    pub fn is_signed_in(&self) -> bool {
+        matches!(self.status, Status::SignedIn)
+    }
+
+    /// Get when the user signed in
+    ///
+    /// Returns `None` if the user is not signed in.
+    /// Long-running applications can use it to decide when to sign in again.
+    pub fn signed_in_since(&self) -> Option<DateTime<Local>> {
+        if self.is_signed_in() {
+            self.signed_in_at
+        } else {
+            None
+        }
+    }
+
+    /// Check if the user can mark presences of students for an event
+    ///
+    /// The list of registered students of the event is requested:
+    /// if the intranet denies access, the user does not have the rights to update presences.
+    ///
+    /// Useful to warn users before starting to mark students, instead of failing when saving changes.
+    pub async fn has_presence_rights(&self, event: &Event) -> Result<bool, Error> {
+        let autologin = match self.autologin() {
+            Some(autologin) if self.is_signed_in() => autologin,
+            _ => return Err(Error::NotSignedIn),
         };
 
+        Ok(event.can_mark(autologin).await?)
+    }
+
+    /// Check if the user can mark presences of students, using the first event of today
+    ///
+    /// If there are no events today, `None` will be returned.
+    pub async fn has_presence_rights_today(&self) -> Result<Option<bool>, Box<dyn error::Error>> {
+        let autologin = match self.autologin() {
+            Some(autologin) if self.is_signed_in() => autologin,
+            _ => return Err(Error::NotSignedIn.into()),
+        };
+
+        let mut events = Vec::new();
+        list_events_today(&mut events, autologin).await?;
+
+        match events.first() {
+            Some(event) => Ok(Some(self.has_presence_rights(event).await?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Check autologin and get its full link
+    fn check_autologin(new: &str) -> Option<String> {
+        static AUTOLOGIN: OnceLock<Regex> = OnceLock::new();
+
+        let re = AUTOLOGIN.get_or_init(|| {
+            Regex::new(r"^(https://intra\.epitech\.eu/auth-|auth-)?([a-z0-9]{40})$")
+                .expect("invalid autologin rule")
+        });
+
         // regex check
-        re.is_match(new)
+        let token = re.captures(new.trim())?.get(2)?.as_str();
+
+        Some(format!("{}/auth-{}", intra::INTRA_URL, token))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::intra::{IntraClient, IntraConfig, MockBackend};
+    use crate::testing::{block_on, serve_once, AUTOLOGIN};
+    use std::error::Error as _;
+
+    const TOKEN: &str = "abcdefghijklmnopqrstuvwxyz1234567890abcd";
+
+    /// Client replying with the user of `AUTOLOGIN`
+    fn user_client() -> IntraClient {
+        let backend = MockBackend::new().with_response(
+            &format!("{}/user?format=json", AUTOLOGIN),
+            r#"{"login": "first.last@epitech.eu", "title": "First Last"}"#,
+        );
+        IntraClient::with_backend(IntraConfig::default(), backend)
+    }
+
+    #[test]
+    fn sign_in_with_mock() {
+        let user = futures::executor::block_on(Auth::from_autologin_with_client(
+            &user_client(),
+            AUTOLOGIN,
+        ))
+        .unwrap();
+        assert_eq!(user.login().as_deref(), Some("first.last@epitech.eu"));
+        assert_eq!(user.autologin().as_deref(), Some(AUTOLOGIN));
+    }
+
+    #[test]
+    fn debug_redacts_autologin() {
+        let user = futures::executor::block_on(Auth::from_autologin_with_client(
+            &user_client(),
+            AUTOLOGIN,
+        ))
+        .unwrap();
+
+        let debug = format!("{:?}", user);
+        assert!(!debug.contains(TOKEN));
+        assert!(debug.contains("first.last@epitech.eu"));
+    }
+
+    #[test]
+    fn intranet_error_as_source() {
+        // intranet replying with too many requests
+        let (base_url, server) = serve_once("429 Too Many Requests", "");
+        let client = IntraClient::with_config(IntraConfig {
+            base_url: Some(base_url),
+            ..IntraConfig::default()
+        });
+
+        let error = block_on(Auth::from_autologin_with_client(&client, AUTOLOGIN)).unwrap_err();
+        server.join().unwrap();
+
+        let source = error
+            .source()
+            .and_then(|e| e.downcast_ref::<intra::Error>());
+        assert!(matches!(source, Some(intra::Error::RateLimit)));
+        assert_eq!(error.to_string(), intra::Error::RateLimit.to_string());
+    }
+
+    #[test]
+    fn check_autologin_forms() {
+        let link = format!("https://intra.epitech.eu/auth-{}", TOKEN);
+
+        assert_eq!(Auth::check_autologin(&link), Some(link.clone()));
+        assert_eq!(
+            Auth::check_autologin(&format!("auth-{}", TOKEN)),
+            Some(link.clone())
+        );
+        assert_eq!(Auth::check_autologin(TOKEN), Some(link.clone()));
+        assert_eq!(Auth::check_autologin(&format!(" {}\n", TOKEN)), Some(link));
+    }
+
+    #[test]
+    fn check_autologin_rejects_other_domains() {
+        let lookalike = format!("https://intraxepitech.eu/auth-{}", TOKEN);
+        assert_eq!(Auth::check_autologin(&lookalike), None);
+
+        let lookalike = format!("https://intra.epitechyeu/auth-{}", TOKEN);
+        assert_eq!(Auth::check_autologin(&lookalike), None);
+    }
+
+    #[test]
+    fn check_autologin_rejects_malformed_tokens() {
+        assert_eq!(Auth::check_autologin(&TOKEN[1..]), None);
+        assert_eq!(Auth::check_autologin(&TOKEN.to_uppercase()), None);
+        assert_eq!(Auth::check_autologin(""), None);
     }
 }
@@ -0,0 +1,183 @@
+//! # Errors
+//!
+//! Single error type wrapping errors of every module,
+//! for callers that use several modules and want to handle their errors in one place.
+//!
+//! Functions of the crate return `Box<dyn std::error::Error>`, it can be converted
+//! to `EpitokError` with `From`, or with `?` in a function returning `EpitokResult`.
+//!
+//! ## Example
+//!
+//! ```
+//! use epitok::error::EpitokResult;
+//! use epitok::event::Code;
+//! use std::error::Error;
+//!
+//! fn parse(code: &str) -> EpitokResult<Code> {
+//!     let code = code.parse::<Code>().map_err(|e| -> Box<dyn Error> { e.into() })?;
+//!     Ok(code)
+//! }
+//!
+//! assert!(parse("/module/2020/B-CPE-110/PAR-1-1/acti-123456/event-654321").is_ok());
+//! ```
+
+use crate::{auth, event, intra, student};
+use std::{error, fmt, io};
+
+/// Result type using `EpitokError`
+pub type EpitokResult<T> = Result<T, EpitokError>;
+
+#[derive(Debug)]
+/// Error possibilities of all modules
+///
+/// The wrapped error can be retrieved with `source`
+pub enum EpitokError {
+    /// Authentication error
+    Auth(auth::Error),
+    /// Event error
+    Event(event::Error),
+    /// Invalid code of event
+    Code(event::CodeError),
+    /// Presence data that can't be saved
+    Validation(event::ValidationError),
+    /// Student error
+    Student(student::Error),
+    /// Intra error
+    Intra(intra::Error),
+    /// Input/output error
+    Io(io::Error),
+    /// Any other error, such as a malformed date
+    Other(Box<dyn error::Error>),
+}
+
+impl error::Error for EpitokError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            EpitokError::Auth(e) => Some(e),
+            EpitokError::Event(e) => Some(e),
+            EpitokError::Code(e) => Some(e),
+            EpitokError::Validation(e) => Some(e),
+            EpitokError::Student(e) => Some(e),
+            EpitokError::Intra(e) => Some(e),
+            EpitokError::Io(e) => Some(e),
+            EpitokError::Other(e) => Some(e.as_ref()),
+        }
+    }
+}
+
+impl fmt::Display for EpitokError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let message = match self {
+            EpitokError::Auth(e) => e.to_string(),
+            EpitokError::Event(e) => e.to_string(),
+            EpitokError::Code(e) => e.to_string(),
+            EpitokError::Validation(e) => e.to_string(),
+            EpitokError::Student(e) => e.to_string(),
+            EpitokError::Intra(e) => e.to_string(),
+            EpitokError::Io(e) => e.to_string(),
+            EpitokError::Other(e) => e.to_string(),
+        };
+        write!(f, "{}", message)
+    }
+}
+
+impl From<auth::Error> for EpitokError {
+    fn from(e: auth::Error) -> Self {
+        EpitokError::Auth(e)
+    }
+}
+
+impl From<event::Error> for EpitokError {
+    fn from(e: event::Error) -> Self {
+        EpitokError::Event(e)
+    }
+}
+
+impl From<event::CodeError> for EpitokError {
+    fn from(e: event::CodeError) -> Self {
+        EpitokError::Code(e)
+    }
+}
+
+impl From<event::ValidationError> for EpitokError {
+    fn from(e: event::ValidationError) -> Self {
+        EpitokError::Validation(e)
+    }
+}
+
+impl From<student::Error> for EpitokError {
+    fn from(e: student::Error) -> Self {
+        EpitokError::Student(e)
+    }
+}
+
+impl From<intra::Error> for EpitokError {
+    fn from(e: intra::Error) -> Self {
+        EpitokError::Intra(e)
+    }
+}
+
+impl From<io::Error> for EpitokError {
+    fn from(e: io::Error) -> Self {
+        EpitokError::Io(e)
+    }
+}
+
+impl From<Box<dyn error::Error>> for EpitokError {
+    /// Get back the error of a module from an error returned by a function of the crate
+    fn from(e: Box<dyn error::Error>) -> Self {
+        let e = match e.downcast::<auth::Error>() {
+            Ok(e) => return EpitokError::Auth(*e),
+            Err(e) => e,
+        };
+        let e = match e.downcast::<event::Error>() {
+            Ok(e) => return EpitokError::Event(*e),
+            Err(e) => e,
+        };
+        let e = match e.downcast::<event::CodeError>() {
+            Ok(e) => return EpitokError::Code(*e),
+            Err(e) => e,
+        };
+        let e = match e.downcast::<event::ValidationError>() {
+            Ok(e) => return EpitokError::Validation(*e),
+            Err(e) => e,
+        };
+        let e = match e.downcast::<student::Error>() {
+            Ok(e) => return EpitokError::Student(*e),
+            Err(e) => e,
+        };
+        let e = match e.downcast::<intra::Error>() {
+            Ok(e) => return EpitokError::Intra(*e),
+            Err(e) => e,
+        };
+        match e.downcast::<io::Error>() {
+            Ok(e) => EpitokError::Io(*e),
+            Err(e) => EpitokError::Other(e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::error::Error as _;
+
+    #[test]
+    fn from_boxed_code_error() {
+        let boxed: Box<dyn error::Error> = "not a code".parse::<event::Code>().unwrap_err().into();
+        let error = EpitokError::from(boxed);
+        assert!(matches!(error, EpitokError::Code(event::CodeError::Format)));
+        assert!(error.source().is_some());
+    }
+
+    #[test]
+    fn from_boxed_intra_error() {
+        let boxed: Box<dyn error::Error> = intra::Error::AccessDenied.into();
+        let error = EpitokError::from(boxed);
+        assert!(matches!(
+            error,
+            EpitokError::Intra(intra::Error::AccessDenied)
+        ));
+        assert_eq!(error.to_string(), intra::Error::AccessDenied.to_string());
+    }
+}
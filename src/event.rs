@@ -7,22 +7,22 @@
 //! ## Example
 //!
 //! ```no_run
-//! use epitok::event::{Event, list_events_today};
-//! use epitok::student::fetch_students;
+//! use epitok::event::events_for;
 //!
 //! # #[async_std::main]
 //! # async fn main() -> Result<(), Box<dyn std::error::Error>> {
 //! let autologin = "https://intra.epitech.eu/auth-autologin";
-//! let mut events: Vec<Event> = Vec::new();
 //!
 //! // Get list of today's events
-//! list_events_today(&mut events, &autologin).await?;
+//! let today = chrono::Local::now().date_naive();
+//! let mut events = events_for(autologin, today).await?;
 //!
 //! // Select the first event
 //! let first_event = &mut events[0];
 //!
 //! // Print information about event
-//! println!("code: {}", first_event.code());
+//! println!("{}", first_event); // [09:00–11:00] Title (Module)
+//! println!("code: {}", first_event.code);
 //! println!("title: {}", first_event.title());
 //! println!("module: {}", first_event.module());
 //!
@@ -33,9 +33,9 @@
 //! first_event.set_all_students_none();
 //!
 //! // Modify presence status to some students
-//! first_event.set_student_present("first.last@epitech.eu");
-//! first_event.set_student_missing("anony.mous@epitech.eu");
-//! first_event.set_student_not_applicable("a.b@epitech.eu");
+//! first_event.set_student_present("first.last@epitech.eu")?;
+//! first_event.set_student_missing("anony.mous@epitech.eu")?;
+//! first_event.set_student_not_applicable("a.b@epitech.eu")?;
 //!
 //! // Upload changes to the intra
 //! first_event.save_changes(autologin).await?;
@@ -50,12 +50,24 @@
 //! ```
 
 use crate::intra;
-use crate::student::{fetch_students, Presence, Student};
-use std::collections::HashMap;
+use crate::student::{
+    fetch_students_with_client, parse_student, parse_students, students_for,
+    students_for_with_client, Presence, Student,
+};
+use chrono::Datelike;
+use futures::stream::{self, StreamExt};
+use regex::Regex;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::convert::TryFrom;
+use std::str::FromStr;
+use std::sync::OnceLock;
 use std::{error, fmt};
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 /// Raw information about event
+///
+/// Two codes are equal if their five components are equal.
 pub struct Code {
     year: String,
     module: String,
@@ -99,9 +111,155 @@ impl Code {
     pub fn event(&self) -> &str {
         &self.event
     }
+
+    /// Build a code from the URL of an event
+    ///
+    /// The URL can be the path returned by `Event::code()`,
+    /// or a full intra URL (with or without the autologin) copied from a browser:
+    /// the query string and the segments after the code of the event (such as `/registered`) are ignored.
+    ///
+    /// # Arguments
+    ///
+    /// * `url` - URL of event, such as `/module/2019/X-XXX-000/XXX-0-0/acti-000000/event-000000`
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use epitok::event::Code;
+    ///
+    /// let url = "https://intra.epitech.eu/module/2019/B-CPE-100/PAR-1-1/acti-123456/event-654321/";
+    /// let code = Code::parse_from_url(url).unwrap();
+    /// assert_eq!(code.to_string(), "/module/2019/B-CPE-100/PAR-1-1/acti-123456/event-654321");
+    /// ```
+    pub fn parse_from_url(url: &str) -> Option<Code> {
+        let start = url.find("/module/")?;
+        let path = url[start..].split(['?', '#']).next()?;
+
+        // keep leading empty segment, `module` and the five components
+        let segments: Vec<&str> = path.split('/').take(7).collect();
+        segments.join("/").parse().ok()
+    }
+
+    /// Check that each component of the code looks like the ones of the intra
+    ///
+    /// The year must have four digits, the module look like `X-XXX-000`, the instance like `XXX-0-0`,
+    /// the activity like `acti-000000` and the event like `event-000000`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use epitok::event::Code;
+    ///
+    /// let code: Code = "/module/2020/G-CUS-000/PAR-0-1/acti-123456/event-654321".parse().unwrap();
+    /// assert!(code.validate().is_ok());
+    /// ```
+    pub fn validate(&self) -> Result<(), CodeError> {
+        match self.invalid_field() {
+            Some((field, value)) => Err(CodeError::Invalid {
+                field,
+                value: value.to_string(),
+            }),
+            None => Ok(()),
+        }
+    }
+
+    /// Get name and value of the first malformed component
+    fn invalid_field(&self) -> Option<(&'static str, &str)> {
+        static YEAR: OnceLock<Regex> = OnceLock::new();
+        static MODULE: OnceLock<Regex> = OnceLock::new();
+        static INSTANCE: OnceLock<Regex> = OnceLock::new();
+        static ACTI: OnceLock<Regex> = OnceLock::new();
+        static EVENT: OnceLock<Regex> = OnceLock::new();
+
+        let rules = [
+            ("year", &YEAR, r"^\d{4}$", &self.year),
+            (
+                "module",
+                &MODULE,
+                r"^[A-Za-z]{1,2}-[A-Za-z0-9]{2,5}-\d{3,4}$",
+                &self.module,
+            ),
+            (
+                "instance",
+                &INSTANCE,
+                r"^[A-Za-z]{2,4}-\d+-\d+$",
+                &self.instance,
+            ),
+            ("acti", &ACTI, r"^acti-\d{6,}$", &self.acti),
+            ("event", &EVENT, r"^event-\d{6,}$", &self.event),
+        ];
+
+        rules.iter().find_map(|(field, re, rule, value)| {
+            // the rules are constant, compiled once
+            let re = re.get_or_init(|| Regex::new(rule).expect("invalid code rule"));
+            if re.is_match(value) {
+                None
+            } else {
+                Some((*field, value.as_str()))
+            }
+        })
+    }
+}
+
+/// Read a code from its URL path, as written by `Display`
+///
+/// Unlike `Code::parse_from_url`, the path must be exactly the one of the event.
+///
+/// # Example
+///
+/// ```
+/// use epitok::event::Code;
+///
+/// let code: Code = "/module/2020/B-CPE-110/PAR-1-1/acti-123456/event-654321".parse().unwrap();
+/// assert_eq!(code.module(), "B-CPE-110");
+/// ```
+impl FromStr for Code {
+    type Err = CodeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let segments: Vec<&str> = s.split('/').collect();
+        let (year, module, instance, acti, event) = match segments.as_slice() {
+            ["", "module", year, module, instance, acti, event]
+                if [year, module, instance, acti, event]
+                    .iter()
+                    .all(|segment| !segment.is_empty()) =>
+            {
+                (*year, *module, *instance, *acti, *event)
+            }
+            _ => return Err(CodeError::Format),
+        };
+
+        let code = Code {
+            year: year.to_string(),
+            module: module.to_string(),
+            instance: instance.to_string(),
+            acti: acti.to_string(),
+            event: event.to_string(),
+        };
+
+        code.validate()?;
+
+        Ok(code)
+    }
+}
+
+/// URL code of event
+///
+/// # Output format
+///
+/// `/module/2019/X-XXX-000/XXX-0-0/acti-000000/event-000000`
+impl fmt::Display for Code {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "/module/{}/{}/{}/{}/{}",
+            self.year, self.module, self.instance, self.acti, self.event
+        )
+    }
 }
 
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 /// # Event
 ///
 /// Information about an event
@@ -113,11 +271,38 @@ pub struct Event {
     /// Module of the event (for clarity)
     module: String,
     /// When event starts
-    start: String,
+    start: chrono::NaiveDateTime,
     /// When event ends
-    end: String,
+    end: chrono::NaiveDateTime,
+    /// Where event takes place
+    #[cfg_attr(feature = "serde", serde(default))]
+    room: Option<Room>,
+    /// Type of activity
+    #[cfg_attr(feature = "serde", serde(default))]
+    kind: ActivityKind,
+    /// Type of activity, as displayed on the intra
+    #[cfg_attr(feature = "serde", serde(default))]
+    kind_label: String,
+    /// Number of registered students
+    #[cfg_attr(feature = "serde", serde(default))]
+    registered_count: Option<usize>,
+    /// Semester of the activity
+    #[cfg_attr(feature = "serde", serde(default))]
+    semester: Option<u8>,
+    /// Description of the activity
+    #[cfg_attr(feature = "serde", serde(default))]
+    description: Option<String>,
+    /// Project the activity is linked to
+    #[cfg_attr(feature = "serde", serde(default))]
+    project: Option<ProjectRef>,
     /// Registered students
     pub students: Vec<Student>,
+    /// Event is an appointment: students register to slots
+    #[cfg_attr(feature = "serde", serde(default))]
+    appointment: bool,
+    /// Slots of appointment
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub slots: Vec<Slot>,
 }
 
 impl Event {
@@ -127,14 +312,7 @@ impl Event {
     ///
     /// `/module/2019/X-XXX-000/XXX-0-0/acti-000000/event-000000`
     pub fn code(&self) -> String {
-        format!(
-            "/module/{}/{}/{}/{}/{}",
-            self.code.year(),
-            self.code.module(),
-            self.code.instance(),
-            self.code.acti(),
-            self.code.event()
-        )
+        self.code.to_string()
     }
 
     /// Get URL to intra pretty page
@@ -152,427 +330,3659 @@ impl Event {
         )
     }
 
-    /// Get name
-    pub fn title(&self) -> &str {
-        &self.title
-    }
-
-    /// Get module name
-    pub fn module(&self) -> &str {
-        &self.module
-    }
-
-    /// Get start time in `HH:MM` format
-    pub fn start(&self) -> &str {
-        &self.start
-    }
-
-    /// Get finish time in `HH:MM` format
-    pub fn end(&self) -> &str {
-        &self.end
-    }
-
-    /// Get mutable list of students
-    pub fn students(&mut self) -> &mut Vec<Student> {
-        &mut self.students
+    /// Get full URL of the intra page of the activity, to open in a browser
+    ///
+    /// The URL uses the base URL of the shared client (see `intra::IntraClient::base_url`),
+    /// it never contains the autologin link.
+    /// The shared client is not created, a client can still be installed afterwards.
+    ///
+    /// # Output format
+    ///
+    /// `https://intra.epitech.eu/module/2019/X-XXX-000/XXX-0-0/acti-000000/`
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use epitok::event::{parse_planning, ListOptions};
+    /// use epitok::intra;
+    ///
+    /// let json = serde_json::json!({
+    ///     "scolaryear": "2020", "codemodule": "B-CPE-110", "codeinstance": "PAR-1-1",
+    ///     "codeacti": "acti-123456", "codeevent": "event-654321",
+    ///     "acti_title": "Bootstrap", "titlemodule": "Unix & C Lab Seminar",
+    ///     "start": "2020-07-01 09:00:00", "end": "2020-07-01 11:00:00", "is_rdv": "0"
+    /// });
+    /// let event = parse_planning(&[json], &ListOptions::new()).events.remove(0);
+    /// println!("{}", event.intra_url());
+    ///
+    /// // the shared client is not created to build the URL
+    /// assert!(intra::IntraClient::install_shared(intra::IntraClient::new()).is_ok());
+    /// ```
+    pub fn intra_url(&self) -> String {
+        self.intra_url_on(intra::IntraClient::shared_base_url())
     }
 
-    /// Set presence type of a student
+    /// Get full URL of the intra page of the activity on an intranet, see `intra_url`
     ///
     /// # Arguments
     ///
-    /// * `login` - Student email address
-    /// * `presence` - Type of presence to set
-    pub fn set_student_presence(&mut self, login: &str, presence: Presence) -> bool {
-        // find student with matching login
-        let student = match self.students.iter_mut().find(|s| s.get_login() == login) {
-            Some(student) => student,
-            None => return false,
-        };
-
-        // update student presence
-        student.set_presence(presence);
-        true
+    /// * `base_url` - URL of the intranet, such as the one of `intra::IntraClient::base_url`
+    pub fn intra_url_on(&self, base_url: &str) -> String {
+        format!("{}{}/", base_url, self.intra_page())
     }
 
-    /// Set student present
+    /// Get full URL of the intra page of the event, to open in a browser
     ///
-    /// # Arguments
+    /// # Output format
     ///
-    /// * `login` - Student email address
-    pub fn set_student_present(&mut self, login: &str) -> bool {
-        self.set_student_presence(login, Presence::Present)
+    /// `https://intra.epitech.eu/module/2019/X-XXX-000/XXX-0-0/acti-000000/event-000000/`
+    pub fn event_url(&self) -> String {
+        self.event_url_on(intra::IntraClient::shared_base_url())
     }
 
-    /// Set student missing
+    /// Get full URL of the intra page of the event on an intranet, see `event_url`
     ///
     /// # Arguments
     ///
-    /// * `login` - Student email address
-    pub fn set_student_missing(&mut self, login: &str) -> bool {
-        self.set_student_presence(login, Presence::Missing)
+    /// * `base_url` - URL of the intranet, such as the one of `intra::IntraClient::base_url`
+    pub fn event_url_on(&self, base_url: &str) -> String {
+        format!("{}{}/", base_url, self.code)
     }
 
-    /// Remove student presence
+    /// Get full URL of the intra page of registered students, where presences are set
     ///
-    /// # Arguments
+    /// # Output format
     ///
-    /// * `login` - Student email address
-    pub fn set_student_none(&mut self, login: &str) -> bool {
-        self.set_student_presence(login, Presence::None)
+    /// `https://intra.epitech.eu/module/2019/X-XXX-000/XXX-0-0/acti-000000/event-000000/registered`
+    pub fn registered_url(&self) -> String {
+        self.registered_url_on(intra::IntraClient::shared_base_url())
     }
 
-    /// Set student N/A
+    /// Get full URL of the intra page of registered students on an intranet, see `registered_url`
     ///
     /// # Arguments
     ///
-    /// * `login` - Student email address
-    pub fn set_student_not_applicable(&mut self, login: &str) -> bool {
-        self.set_student_presence(login, Presence::NotApplicable)
+    /// * `base_url` - URL of the intranet, such as the one of `intra::IntraClient::base_url`
+    pub fn registered_url_on(&self, base_url: &str) -> String {
+        format!("{}{}/registered", base_url, self.code)
     }
 
-    fn set_all_students_presence(&mut self, presence: Presence) {
-        let students = self.students.iter_mut();
+    /// Get name
+    pub fn title(&self) -> &str {
+        &self.title
+    }
 
-        for student in students {
-            student.set_presence(presence);
-        }
+    /// Get module name
+    pub fn module(&self) -> &str {
+        &self.module
     }
 
-    /// Set all students as present
-    pub fn set_all_students_present(&mut self) {
-        self.set_all_students_presence(Presence::Present);
+    /// Get description of the activity, if known
+    ///
+    /// Events from the planning don't have a description, see `get_event` and `get_activity`.
+    pub fn description(&self) -> Option<&str> {
+        self.description.as_deref()
     }
 
-    /// Set all students as missing
-    pub fn set_all_students_missing(&mut self) {
-        self.set_all_students_presence(Presence::Missing);
+    /// Get project the activity is linked to, if known
+    ///
+    /// Events from the planning don't have a project, see `get_event` and `get_activity`.
+    pub fn project(&self) -> Option<&ProjectRef> {
+        self.project.as_ref()
     }
 
-    /// Remove presences for all students
-    pub fn set_all_students_none(&mut self) {
-        self.set_all_students_presence(Presence::None);
+    /// Check if event is an appointment (follow-up, defense...)
+    ///
+    /// Students of an appointment register to slots: use `fetch_slots` instead of `fetch_students`,
+    /// and `save_slots` instead of `save_changes`.
+    pub fn is_appointment(&self) -> bool {
+        self.appointment
     }
 
-    /// Set presences for all students as Not applicable
-    pub fn set_all_students_not_applicable(&mut self) {
-        self.set_all_students_presence(Presence::NotApplicable);
+    /// Get mutable list of slots of an appointment
+    pub fn slots(&mut self) -> &mut Vec<Slot> {
+        &mut self.slots
     }
 
-    fn set_remaining_students_presence(&mut self, presence: Presence) {
-        let students = self.students.iter_mut();
+    /// Fetch slots of an appointment, with their registered students
+    ///
+    /// # Return value
+    /// On success the number of slots will be returned.
+    pub async fn fetch_slots(&mut self, autologin: &str) -> Result<usize, Box<dyn error::Error>> {
+        let url = format!("{}{}/rdv/?format=json", autologin, self.intra_page());
 
-        for student in students {
-            if let Presence::None = student.get_presence() {
-                student.set_presence(presence)
+        let json = intra::get_obj(&url).await?;
+
+        let mut slots = Vec::new();
+
+        // slots are grouped by blocks
+        for block in json["slots"].as_array().into_iter().flatten() {
+            for slot in block["slots"].as_array().into_iter().flatten() {
+                slots.push(parse_slot(slot)?);
             }
         }
-    }
 
-    /// Set students who do not have a presence status as present
-    pub fn set_remaining_students_present(&mut self) {
-        self.set_remaining_students_presence(Presence::Present)
+        self.slots = slots;
+        Ok(self.slots.len())
     }
 
-    /// Set students who do not have a presence status as missing
-    pub fn set_remaining_students_missing(&mut self) {
-        self.set_remaining_students_presence(Presence::Missing)
+    /// Save presences of students registered to slots of an appointment
+    pub async fn save_slots(&mut self, autologin: &str) -> Result<(), Box<dyn error::Error>> {
+        let mut hm = HashMap::new();
+
+        let members = self.slots.iter().flat_map(|slot| slot.members.iter());
+        for (i, student) in members.enumerate() {
+            hm.insert(
+                format!("items[{}][login]", i),
+                student.get_login().to_string(),
+            );
+            hm.insert(
+                format!("items[{}][present]", i),
+                student.get_presence().intra_value().to_string(),
+            );
+        }
+
+        intra::update_presences(autologin, self.code().as_str(), hm).await?;
+
+        Ok(())
     }
 
-    /// Fetch list of students from an existing event
+    /// Get number of registered students
     ///
-    /// By default when you fetch an event, its students list is empty.
-    /// It can be populated using this function.
-    pub async fn fetch_students(
-        &mut self,
-        autologin: &str,
-    ) -> Result<usize, Box<dyn error::Error>> {
-        let code = self.code();
-        let students = self.students();
-        fetch_students(students, autologin, &code).await
+    /// It is known without fetching the list of students,
+    /// and it is updated with the number of fetched students after `fetch_students`.
+    pub fn registered_count(&self) -> Option<usize> {
+        self.registered_count
     }
 
-    /// Export registered students to intra format (to be uploaded)
+    /// Get semester of the activity, if known
     ///
-    /// The intra API uses `url-encoded` forms as a format to upload students and their statuses:
-    /// - `items[x][login]=first.last@epitech.eu`
-    /// - `items[x][present]=presence`
+    /// # Example
     ///
-    /// where
-    /// - `x` is the position of the student in the array
-    /// - `first.last@epitech.eu` is the email of the student
-    /// - `presence` is the presence status of the student (see `student::Presence` for more information)
-    fn export_students(&self) -> HashMap<String, String> {
-        let mut hm = HashMap::new();
+    /// ```
+    /// # use epitok::event::Event;
+    /// # fn example(event: &Event) {
+    /// match event.semester() {
+    ///     Some(semester) => println!("semester {}", semester),
+    ///     None => println!("year {:?}", event.promo_hint()),
+    /// }
+    /// # }
+    /// ```
+    pub fn semester(&self) -> Option<u8> {
+        self.semester
+    }
 
-        for (i, student) in self.students.iter().enumerate() {
-            // student login
-            let login_k = format!("items[{}][login]", i);
-            let login_v = student.get_login().to_string();
-            hm.insert(login_k, login_v);
+    /// Guess year of study of students of the activity, from the code of the module
+    ///
+    /// The first digit of the number of a module tells the year it is for:
+    /// `B-CPE-110` is a module of the first year.
+    pub fn promo_hint(&self) -> Option<u8> {
+        let number = self.code.module().rsplit('-').next()?;
+        let year = number.chars().next()?.to_digit(10)?;
 
-            // student presence value
-            let presence_k = format!("items[{}][present]", i);
-            let presence_v = student.get_presence().to_string();
-            hm.insert(presence_k, presence_v);
+        match year {
+            1..=5 => u8::try_from(year).ok(),
+            _ => None,
         }
-        hm
     }
 
-    /// Save changes to the intra (upload them)
+    /// Find the position of the event in the sessions of its activity
     ///
     /// # Arguments
     ///
-    /// * `autologin` - Autologin link. If you use the `epitok::auth::Auth` struct, use its `get_autologin` method
+    /// * `sessions` - Sessions of the activity, from `activity_sessions`
     ///
-    pub async fn save_changes(&mut self, autologin: &str) -> Result<(), Box<dyn error::Error>> {
-        // export students to intra format
-        let students = self.export_students();
+    /// # Return value
+    /// The number of the session (starting at 1) and the number of sessions,
+    /// or `None` if the event is not in the list.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use epitok::event::{activity_sessions, get_event_by_code, Code};
+    ///
+    /// # #[async_std::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let autologin = "https://intra.epitech.eu/auth-abcdefghijklmnopqrstuvwxyz1234567890abcd";
+    /// let code: Code = "/module/2020/B-PSU-100/PAR-1-1/acti-123456/event-000002".parse()?;
+    /// let event = get_event_by_code(autologin, &code).await?;
+    /// let sessions = activity_sessions(autologin, &code).await?;
+    ///
+    /// if let Some((number, count)) = event.session_index(&sessions) {
+    ///     println!("session {} of {}", number, count);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn session_index(&self, sessions: &[SessionRef]) -> Option<(usize, usize)> {
+        let index = sessions
+            .iter()
+            .position(|session| session.code == self.code)?;
 
-        // upload and check intra reply
-        intra::update_presences(autologin, self.code().as_str(), students).await?;
+        Some((index + 1, sessions.len()))
+    }
 
-        Ok(())
+    /// Get type of activity
+    pub fn kind(&self) -> &ActivityKind {
+        &self.kind
     }
-}
 
-#[derive(Debug)]
-/// Error possibilities
-pub enum Error {
-    /// Event does not have a URL
-    EventURL,
-    /// Event does not have a title
-    Title,
-    /// Event is not linked to any modules
-    Module,
-    /// Event does not have a starting time
-    TimeStart,
-    /// Event does not have a finish time
-    TimeEnd,
-}
+    /// Get type of activity, as displayed on the intra
+    pub fn kind_label(&self) -> &str {
+        &self.kind_label
+    }
 
-impl error::Error for Error {}
+    /// Get room, `None` if event does not take place in a room (remote sessions for example)
+    pub fn room(&self) -> Option<&Room> {
+        self.room.as_ref()
+    }
 
-impl fmt::Display for Error {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        let message = match *self {
-            Error::EventURL => "This event doesn't have a url",
-            Error::Title => "This event does not have a title",
-            Error::Module => "This event does not belong to a module",
-            Error::TimeStart => "This event does not have a starting time",
-            Error::TimeEnd => "This event does not have a finish time",
-        };
-        write!(f, "{}", message)
+    /// Get day of event, the day it starts
+    pub fn date(&self) -> chrono::NaiveDate {
+        self.start.date()
     }
-}
 
-#[derive(Debug)]
-/// Time representation
-enum Time {
-    /// Event start
-    Start,
-    /// Event end
-    End,
-}
+    /// Get day of event in `YYYY-MM-DD` format
+    pub fn date_str(&self) -> String {
+        self.date().format("%Y-%m-%d").to_string()
+    }
 
-/// Parse start or end time from JSON
-fn parse_time(json: &serde_json::Value, time: Time) -> Option<String> {
-    let time = match time {
-        Time::Start => "start",
-        Time::End => "end",
-    };
+    /// Get date and time when event starts
+    pub fn start(&self) -> &chrono::NaiveDateTime {
+        &self.start
+    }
 
-    match json[time].as_str() {
-        Some(start) => match chrono::NaiveDateTime::parse_from_str(&start, "%Y-%m-%d %H:%M:%S") {
-            Ok(start) => Some(start.format("%H:%M").to_string()),
-            Err(_) => None,
-        },
-        None => None,
+    /// Get date and time when event ends
+    pub fn end(&self) -> &chrono::NaiveDateTime {
+        &self.end
     }
-}
 
-/// Gather code elements making event intra information
-fn construct_code(json: &serde_json::Value) -> Option<Code> {
-    Code {
-        year: json["scolaryear"].as_str()?.into(),
-        module: json["codemodule"].as_str()?.into(),
-        instance: json["codeinstance"].as_str()?.into(),
-        acti: json["codeacti"].as_str()?.into(),
-        event: json["codeevent"].as_str()?.into(),
+    /// Get start time in `HH:MM` format
+    pub fn start_time_str(&self) -> String {
+        self.start.format("%H:%M").to_string()
     }
-    .into()
-}
 
-/// Show events of a particular date
-///
-/// # Arguments
-///
-/// * `list` - Where events will be stored
+    /// Get finish time in `HH:MM` format
+    pub fn end_time_str(&self) -> String {
+        self.end.format("%H:%M").to_string()
+    }
+
+    /// Get start time
+    pub fn start_time(&self) -> chrono::NaiveTime {
+        self.start.time()
+    }
+
+    /// Get finish time
+    pub fn end_time(&self) -> chrono::NaiveTime {
+        self.end.time()
+    }
+
+    /// Get date and time when event starts
+    pub fn start_datetime(&self) -> chrono::NaiveDateTime {
+        self.start
+    }
+
+    /// Get date and time when event ends
+    pub fn end_datetime(&self) -> chrono::NaiveDateTime {
+        self.end
+    }
+
+    /// Get how long event lasts
+    ///
+    /// If the event ends before it starts (it happens on the intra), the duration will be zero.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use epitok::event::Event;
+    /// # fn example(event: &Event) {
+    /// println!("{} lasts {} minutes", event, event.duration().num_minutes());
+    /// # }
+    /// ```
+    pub fn duration(&self) -> chrono::Duration {
+        if self.end < self.start {
+            return chrono::Duration::zero();
+        }
+
+        self.end - self.start
+    }
+
+    /// Check if event has started at a given time
+    ///
+    /// An event has started at its exact start time.
+    pub fn has_started_at(&self, now: chrono::NaiveDateTime) -> bool {
+        now >= self.start
+    }
+
+    /// Check if event has ended at a given time
+    ///
+    /// An event has ended at its exact end time.
+    pub fn has_ended_at(&self, now: chrono::NaiveDateTime) -> bool {
+        now >= self.end
+    }
+
+    /// Check if event is happening at a given time
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use epitok::event::Event;
+    /// # fn example(event: &Event) {
+    /// let noon = chrono::NaiveDate::from_ymd_opt(2020, 7, 1).unwrap().and_hms_opt(12, 0, 0).unwrap();
+    /// if event.is_ongoing_at(noon) {
+    ///     println!("{} is happening at noon", event);
+    /// }
+    /// # }
+    /// ```
+    pub fn is_ongoing_at(&self, now: chrono::NaiveDateTime) -> bool {
+        self.has_started_at(now) && !self.has_ended_at(now)
+    }
+
+    /// Check if event is finished at a given time
+    pub fn is_past_at(&self, now: chrono::NaiveDateTime) -> bool {
+        self.has_ended_at(now)
+    }
+
+    /// Check if event has not started yet at a given time
+    pub fn is_future_at(&self, now: chrono::NaiveDateTime) -> bool {
+        !self.has_started_at(now)
+    }
+
+    /// Check if event has started
+    pub fn has_started(&self) -> bool {
+        self.has_started_at(chrono::Local::now().naive_local())
+    }
+
+    /// Check if event has ended
+    pub fn has_ended(&self) -> bool {
+        self.has_ended_at(chrono::Local::now().naive_local())
+    }
+
+    /// Check if event is happening now
+    pub fn is_ongoing(&self) -> bool {
+        self.is_ongoing_at(chrono::Local::now().naive_local())
+    }
+
+    /// Check if event is finished
+    pub fn is_past(&self) -> bool {
+        self.is_past_at(chrono::Local::now().naive_local())
+    }
+
+    /// Check if event has not started yet
+    pub fn is_future(&self) -> bool {
+        self.is_future_at(chrono::Local::now().naive_local())
+    }
+
+    /// Get mutable list of students
+    pub fn students(&mut self) -> &mut Vec<Student> {
+        &mut self.students
+    }
+
+    /// Get students with a presence status
+    pub fn students_with_presence(&self, presence: Presence) -> Vec<&Student> {
+        self.students
+            .iter()
+            .filter(|s| *s.get_presence() == presence)
+            .collect()
+    }
+
+    /// Get students who do not have a presence status
+    pub fn students_without_presence(&self, presence: Presence) -> Vec<&Student> {
+        self.students
+            .iter()
+            .filter(|s| *s.get_presence() != presence)
+            .collect()
+    }
+
+    /// Sort students alphabetically by name
+    pub fn sort_students_by_name(&mut self) {
+        self.students.sort_by(|a, b| a.get_name().cmp(b.get_name()));
+    }
+
+    /// Sort students by presence status
+    ///
+    /// Order: present, late, no status, missing, excused, N/A, failed.
+    ///
+    /// The sort is stable: call `sort_students_by_name` first to sort students by name in each group.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use epitok::event::Event;
+    /// # fn example(event: &mut Event) {
+    /// // present students first, by name in each group
+    /// event.sort_students_by_name();
+    /// event.sort_students_by_presence();
+    /// # }
+    /// ```
+    pub fn sort_students_by_presence(&mut self) {
+        fn order(presence: &Presence) -> u8 {
+            match presence {
+                Presence::Present => 0,
+                Presence::Late => 1,
+                Presence::None => 2,
+                Presence::Missing => 3,
+                Presence::Excused => 4,
+                Presence::NotApplicable => 5,
+                Presence::Failed => 6,
+            }
+        }
+
+        self.students
+            .sort_by_key(|student| order(student.get_presence()));
+    }
+
+    /// Check if a student is registered to the event
+    ///
+    /// # Arguments
+    ///
+    /// * `login` - Student email address
+    pub fn has_student(&self, login: &str) -> bool {
+        self.get_student(login).is_some()
+    }
+
+    /// Get a registered student
+    ///
+    /// # Arguments
+    ///
+    /// * `login` - Student email address
+    pub fn get_student(&self, login: &str) -> Option<&Student> {
+        self.students.iter().find(|s| s.get_login() == login)
+    }
+
+    /// Get a registered student, to modify it
+    ///
+    /// # Arguments
+    ///
+    /// * `login` - Student email address
+    pub fn get_student_mut(&mut self, login: &str) -> Option<&mut Student> {
+        self.students.iter_mut().find(|s| s.get_login() == login)
+    }
+
+    /// Get registered students, without staff members
+    fn students_only(&self) -> impl Iterator<Item = &Student> {
+        self.students.iter().filter(|s| !s.is_staff())
+    }
+
+    /// Get number of registered students, staff members excluded
+    pub fn count_students(&self) -> usize {
+        self.students_only().count()
+    }
+
+    /// Get number of students set as present, late students included
+    pub fn count_present(&self) -> usize {
+        self.students_only()
+            .filter(|s| s.get_presence().is_present())
+            .count()
+    }
+
+    /// Get number of students set as missing
+    pub fn count_missing(&self) -> usize {
+        self.students_only()
+            .filter(|s| matches!(s.get_presence(), Presence::Missing))
+            .count()
+    }
+
+    /// Get number of students set as N/A
+    pub fn count_not_applicable(&self) -> usize {
+        self.students_only()
+            .filter(|s| matches!(s.get_presence(), Presence::NotApplicable))
+            .count()
+    }
+
+    /// Get number of students whose presence failed to be saved
+    pub fn count_failed(&self) -> usize {
+        self.students_only()
+            .filter(|s| matches!(s.get_presence(), Presence::Failed))
+            .count()
+    }
+
+    /// Get fraction of students set as present or late, between `0.0` and `1.0`
+    ///
+    /// Staff members are not counted.
+    /// If there are no students, `0.0` will be returned.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use epitok::event::Event;
+    /// # fn example(event: &Event) {
+    /// println!("{} of {} students present", event.count_present(), event.count_students());
+    /// println!("attendance: {:.0}%", event.attendance_rate() * 100.0);
+    /// # }
+    /// ```
+    pub fn attendance_rate(&self) -> f64 {
+        let students = self.count_students();
+        if students == 0 {
+            return 0.0;
+        }
+
+        self.count_present() as f64 / students as f64
+    }
+
+    /// Set presence type of a student
+    ///
+    /// # Arguments
+    ///
+    /// * `login` - Student email address
+    /// * `presence` - Type of presence to set
+    ///
+    /// # Errors
+    ///
+    /// If the student is not registered to the event, `Error::StudentNotFound` is returned
+    ///
+    /// ```
+    /// # use epitok::event::Event;
+    /// use epitok::student::Presence;
+    ///
+    /// # fn example(event: &mut Event) -> Result<(), epitok::event::Error> {
+    /// event.set_student_presence("first.last@epitech.eu", Presence::Late)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn set_student_presence(&mut self, login: &str, presence: Presence) -> Result<(), Error> {
+        // find student with matching login
+        let student = match self.get_student_mut(login) {
+            Some(student) => student,
+            None => return Err(Error::StudentNotFound(login.to_string())),
+        };
+
+        // update student presence
+        student.set_presence(presence);
+        Ok(())
+    }
+
+    /// Set student present
+    ///
+    /// # Arguments
+    ///
+    /// * `login` - Student email address
+    pub fn set_student_present(&mut self, login: &str) -> Result<(), Error> {
+        self.set_student_presence(login, Presence::Present)
+    }
+
+    /// Set student missing
+    ///
+    /// # Arguments
+    ///
+    /// * `login` - Student email address
+    pub fn set_student_missing(&mut self, login: &str) -> Result<(), Error> {
+        self.set_student_presence(login, Presence::Missing)
+    }
+
+    /// Remove student presence
+    ///
+    /// # Arguments
+    ///
+    /// * `login` - Student email address
+    pub fn set_student_none(&mut self, login: &str) -> Result<(), Error> {
+        self.set_student_presence(login, Presence::None)
+    }
+
+    /// Set student N/A
+    ///
+    /// # Arguments
+    ///
+    /// * `login` - Student email address
+    pub fn set_student_not_applicable(&mut self, login: &str) -> Result<(), Error> {
+        self.set_student_presence(login, Presence::NotApplicable)
+    }
+
+    /// Set student late
+    ///
+    /// The student will be uploaded as present.
+    ///
+    /// # Arguments
+    ///
+    /// * `login` - Student email address
+    pub fn set_student_late(&mut self, login: &str) -> Result<(), Error> {
+        self.set_student_presence(login, Presence::Late)
+    }
+
+    /// Set student excused
+    ///
+    /// The student will be uploaded as N/A.
+    ///
+    /// # Arguments
+    ///
+    /// * `login` - Student email address
+    pub fn set_student_excused(&mut self, login: &str) -> Result<(), Error> {
+        self.set_student_presence(login, Presence::Excused)
+    }
+
+    /// Set presence of several students
+    ///
+    /// # Arguments
+    ///
+    /// * `map` - Student email addresses and the type of presence to set
+    ///
+    /// # Return value
+    ///
+    /// The number of updated students and the logins that are not registered to the event
+    pub fn import_presence_from_map(
+        &mut self,
+        map: &HashMap<String, Presence>,
+    ) -> (usize, Vec<String>) {
+        let mut updated = 0;
+        let mut unknown = Vec::new();
+
+        for (login, presence) in map {
+            match self.set_student_presence(login, *presence) {
+                Ok(()) => updated += 1,
+                Err(_) => unknown.push(login.clone()),
+            }
+        }
+
+        (updated, unknown)
+    }
+
+    /// Copy presence statuses of students from another event
+    ///
+    /// Only students registered to both events are updated.
+    ///
+    /// # Return value
+    ///
+    /// The number of updated students
+    pub fn apply_presence_from(&mut self, other: &Event) -> usize {
+        let mut updated = 0;
+
+        for student in other.students.iter() {
+            if let Some(s) = self.get_student_mut(student.get_login()) {
+                s.set_presence(*student.get_presence());
+                updated += 1;
+            }
+        }
+
+        updated
+    }
+
+    /// Compare presence statuses of students with another event
+    ///
+    /// Students are matched by login.
+    /// A student registered to only one of the events has no presence (`None`) in the other one.
+    ///
+    /// # Return value
+    ///
+    /// A list of the students whose presence is different
+    pub fn diff(&self, other: &Event) -> Vec<PresenceDiff> {
+        let others: HashMap<&str, &Presence> = other
+            .students
+            .iter()
+            .map(|s| (s.get_login(), s.get_presence()))
+            .collect();
+
+        let mut diff = Vec::new();
+
+        for student in self.students.iter() {
+            let before = Some(*student.get_presence());
+            let after = others.get(student.get_login()).map(|p| **p);
+
+            if before != after {
+                diff.push(PresenceDiff {
+                    login: student.get_login().to_string(),
+                    before,
+                    after,
+                });
+            }
+        }
+
+        // students only registered to the other event
+        for student in other.students.iter() {
+            if !self.has_student(student.get_login()) {
+                diff.push(PresenceDiff {
+                    login: student.get_login().to_string(),
+                    before: None,
+                    after: Some(*student.get_presence()),
+                });
+            }
+        }
+
+        diff
+    }
+
+    /// Get students registered to the event but not to its module
+    ///
+    /// Logins are compared without case.
+    ///
+    /// # Arguments
+    ///
+    /// * `roster` - Students registered to the module, see `student::fetch_module_roster`
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use epitok::event::get_event_by_path;
+    /// use epitok::student::fetch_module_roster;
+    ///
+    /// # #[async_std::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let autologin = "https://intra.epitech.eu/auth-abcdefghijklmnopqrstuvwxyz1234567890abcd";
+    /// let mut event = get_event_by_path(autologin, "/module/2020/B-CPE-110/PAR-1-1/acti-123456/event-654321").await?;
+    /// event.fetch_students(autologin).await?;
+    ///
+    /// let roster = fetch_module_roster(autologin, "2020", "B-CPE-110", "PAR-1-1").await?;
+    /// for student in event.missing_from_module(&roster) {
+    ///     println!("{} is not registered to the module", student.get_login());
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn missing_from_module(&self, roster: &[Student]) -> Vec<&Student> {
+        let logins = lowercase_logins(roster);
+
+        self.students
+            .iter()
+            .filter(|student| !logins.contains(&student.get_login().to_lowercase()))
+            .collect()
+    }
+
+    /// Get students registered to the module of the event but not to the event
+    ///
+    /// Logins are compared without case.
+    ///
+    /// # Arguments
+    ///
+    /// * `roster` - Students registered to the module, see `student::fetch_module_roster`
+    pub fn unregistered_from_module<'a>(&self, roster: &'a [Student]) -> Vec<&'a Student> {
+        let logins = lowercase_logins(&self.students);
+
+        roster
+            .iter()
+            .filter(|student| !logins.contains(&student.get_login().to_lowercase()))
+            .collect()
+    }
+
+    fn set_all_students_presence(&mut self, presence: Presence) {
+        let students = self.students.iter_mut();
+
+        for student in students {
+            student.set_presence(presence);
+        }
+    }
+
+    /// Set all students as present
+    pub fn set_all_students_present(&mut self) {
+        self.set_all_students_presence(Presence::Present);
+    }
+
+    /// Set all students as missing
+    pub fn set_all_students_missing(&mut self) {
+        self.set_all_students_presence(Presence::Missing);
+    }
+
+    /// Remove presences for all students
+    pub fn set_all_students_none(&mut self) {
+        self.set_all_students_presence(Presence::None);
+    }
+
+    /// Set presences for all students as Not applicable
+    pub fn set_all_students_not_applicable(&mut self) {
+        self.set_all_students_presence(Presence::NotApplicable);
+    }
+
+    fn set_remaining_students_presence(&mut self, presence: Presence) {
+        let students = self.students.iter_mut();
+
+        for student in students {
+            if let Presence::None = student.get_presence() {
+                student.set_presence(presence);
+            }
+        }
+    }
+
+    /// Set students who do not have a presence status as present
+    pub fn set_remaining_students_present(&mut self) {
+        self.set_remaining_students_presence(Presence::Present)
+    }
+
+    /// Set students who do not have a presence status as missing
+    pub fn set_remaining_students_missing(&mut self) {
+        self.set_remaining_students_presence(Presence::Missing)
+    }
+
+    /// Export students and their presence statuses to CSV
+    ///
+    /// The first line is a header: `login,name,presence`,
+    /// presence statuses are written like `Presence` displays them (`present`, `absent`, `late`, ...),
+    /// so `import_presence_from_csv` reads them back.
+    #[cfg(feature = "csv")]
+    pub fn export_to_csv(&self) -> Result<String, Box<dyn error::Error>> {
+        let mut writer = csv::Writer::from_writer(Vec::new());
+
+        writer.write_record(["login", "name", "presence"])?;
+        for student in self.students.iter() {
+            writer.write_record([
+                student.get_login(),
+                student.get_name(),
+                &student.get_presence().to_string(),
+            ])?;
+        }
+
+        Ok(String::from_utf8(writer.into_inner()?)?)
+    }
+
+    /// Import presence statuses from CSV
+    ///
+    /// The first column is the student login, the last column is the presence status.
+    /// The header line is optional.
+    ///
+    /// # Return value
+    ///
+    /// On success the number of updated students, the logins that are not registered
+    /// to the event and the rows whose presence status can't be read will be returned.
+    /// Students of these rows are left untouched.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use epitok::event::Event;
+    /// # fn example(event: &mut Event) -> Result<(), Box<dyn std::error::Error>> {
+    /// let csv = "login,presence\nfirst.last@epitech.eu,present\nanony.mous@epitech.eu,absent\n";
+    ///
+    /// let import = event.import_presence_from_csv(csv)?;
+    /// println!("{} students updated", import.updated);
+    /// for login in import.unknown {
+    ///     println!("{} is not registered", login);
+    /// }
+    /// for (login, presence) in import.invalid {
+    ///     println!("{}: unknown presence {}", login, presence);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "csv")]
+    pub fn import_presence_from_csv(
+        &mut self,
+        csv: &str,
+    ) -> Result<CsvImport, Box<dyn error::Error>> {
+        let mut reader = csv::ReaderBuilder::new()
+            .has_headers(false)
+            .flexible(true)
+            .trim(csv::Trim::All)
+            .from_reader(csv.as_bytes());
+
+        let mut import = CsvImport::default();
+
+        for (i, record) in reader.records().enumerate() {
+            let record = record?;
+
+            let login = match record.get(0) {
+                Some(login) if !login.is_empty() => login,
+                _ => continue, // empty line
+            };
+
+            // skip header
+            if i == 0 && login.eq_ignore_ascii_case("login") {
+                continue;
+            }
+
+            let presence = match record.iter().skip(1).last() {
+                Some("") | None => Presence::None,
+                Some(presence) => match presence.parse::<Presence>() {
+                    Ok(presence) => presence,
+                    Err(_) => {
+                        import
+                            .invalid
+                            .push((login.to_string(), presence.to_string()));
+                        continue;
+                    }
+                },
+            };
+
+            match self.set_student_presence(login, presence) {
+                Ok(()) => import.updated += 1,
+                Err(_) => import.unknown.push(login.to_string()),
+            }
+        }
+
+        Ok(import)
+    }
+
+    /// Check if the user can mark presences of students for this event
+    ///
+    /// The list of registered students of the event is requested:
+    /// if the intranet denies access, the user does not have the rights to update presences.
+    pub async fn can_mark(&self, autologin: &str) -> Result<bool, intra::Error> {
+        self.can_mark_with_client(intra::IntraClient::shared(), autologin)
+            .await
+    }
+
+    /// Check if the user can mark presences of students for this event, with a client instead of the shared one
+    ///
+    /// See `can_mark`.
+    ///
+    /// # Arguments
+    ///
+    /// * `client` - Client requests are made with
+    /// * `autologin` - User autologin link
+    pub async fn can_mark_with_client(
+        &self,
+        client: &intra::IntraClient,
+        autologin: &str,
+    ) -> Result<bool, intra::Error> {
+        let url = format!("{}{}/registered?format=json", autologin, self.code());
+
+        match client.get_array_obj(&url).await {
+            Ok(_) => Ok(true),
+            Err(intra::Error::Empty) => Ok(true), // No students have signed up for this event
+            Err(intra::Error::AccessDenied) => Ok(false),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Get list of students registered to the event, without storing them in the event
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use chrono::NaiveDate;
+    /// use epitok::event::events_for;
+    ///
+    /// # #[async_std::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let autologin = "https://intra.epitech.eu/auth-abcdefghijklmnopqrstuvwxyz1234567890abcd";
+    /// let date = NaiveDate::from_ymd_opt(2020, 7, 1).unwrap();
+    ///
+    /// for event in events_for(autologin, date).await? {
+    ///     let students = event.students_for(autologin).await?;
+    ///     println!("{}: {} students", event, students.len());
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn students_for(
+        &self,
+        autologin: &str,
+    ) -> Result<Vec<Student>, Box<dyn error::Error>> {
+        students_for(autologin, &self.code()).await
+    }
+
+    /// Fetch list of students from an existing event
+    ///
+    /// By default when you fetch an event, its students list is empty.
+    /// It can be populated using this function.
+    pub async fn fetch_students(
+        &mut self,
+        autologin: &str,
+    ) -> Result<usize, Box<dyn error::Error>> {
+        self.fetch_students_with_client(intra::IntraClient::shared(), autologin)
+            .await
+    }
+
+    /// Fetch list of students from an existing event, with a client instead of the shared one
+    ///
+    /// See `fetch_students`.
+    ///
+    /// # Arguments
+    ///
+    /// * `client` - Client requests are made with
+    /// * `autologin` - User autologin link
+    pub async fn fetch_students_with_client(
+        &mut self,
+        client: &intra::IntraClient,
+        autologin: &str,
+    ) -> Result<usize, Box<dyn error::Error>> {
+        let code = self.code();
+        let students = self.students();
+        let number_students =
+            fetch_students_with_client(client, students, autologin, &code).await?;
+        self.registered_count = Some(number_students);
+        Ok(number_students)
+    }
+
+    /// Fetch list of students of several events concurrently
+    ///
+    /// Requests are made at the same time like with `intra::parallel_get`,
+    /// the students of each event are replaced like with `fetch_students`.
+    ///
+    /// # Arguments
+    ///
+    /// * `events` - Events to fetch the students of
+    /// * `autologin` - User autologin link
+    ///
+    /// # Return value
+    /// The number of students of each event is returned, in the same order as the events.
+    /// Students of an event are left untouched if they can't be fetched.
+    pub async fn fetch_students_all(
+        events: &mut [Event],
+        autologin: &str,
+    ) -> Vec<Result<usize, Box<dyn error::Error>>> {
+        let urls = events
+            .iter()
+            .map(|event| format!("{}{}/registered?format=json", autologin, event.code))
+            .collect();
+
+        let replies = intra::parallel_get(intra::IntraClient::shared(), urls).await;
+
+        events
+            .iter_mut()
+            .zip(replies)
+            .map(|(event, reply)| {
+                let students = match reply? {
+                    serde_json::Value::Array(json) => parse_students(&json)?,
+                    // no students have signed up for this event
+                    _ => Vec::new(),
+                };
+
+                event.registered_count = Some(students.len());
+                event.students = students;
+                Ok(event.students.len())
+            })
+            .collect()
+    }
+
+    /// Fetch list of students again, discarding local changes
+    ///
+    /// **This is destructive**: presence statuses that have not been saved are lost.
+    pub async fn reload_students(
+        &mut self,
+        autologin: &str,
+    ) -> Result<usize, Box<dyn error::Error>> {
+        self.fetch_students(autologin).await
+    }
+
+    /// Check if presences of students have been modified and not saved yet
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use epitok::event::Event;
+    /// # fn example(event: &Event) {
+    /// if event.students_dirty() {
+    ///     println!("{} has presences that are not saved", event);
+    /// }
+    /// # }
+    /// ```
+    pub fn students_dirty(&self) -> bool {
+        self.students.iter().any(Student::is_modified)
+    }
+
+    /// Export registered students to intra format (to be uploaded)
+    ///
+    /// The intra API uses `url-encoded` forms as a format to upload students and their statuses:
+    /// - `items[x][login]=first.last@epitech.eu`
+    /// - `items[x][present]=presence`
+    ///
+    /// where
+    /// - `x` is the position of the student in the array
+    /// - `first.last@epitech.eu` is the email of the student
+    /// - `presence` is the presence status of the student (see `student::Presence` for more information)
+    ///
+    /// Only students kept by `options` are exported.
+    fn export_students(&self, options: &SaveOptions) -> HashMap<String, String> {
+        let mut hm = HashMap::new();
+
+        let students = self
+            .students
+            .iter()
+            .filter(|student| options.keeps(student));
+
+        for (i, student) in students.enumerate() {
+            // student login
+            let login_k = format!("items[{}][login]", i);
+            let login_v = student.get_login().to_string();
+            hm.insert(login_k, login_v);
+
+            // student presence value
+            let presence_k = format!("items[{}][present]", i);
+            let presence_v = student.get_presence().intra_value().to_string();
+            hm.insert(presence_k, presence_v);
+        }
+        hm
+    }
+
+    /// Get changes that would be uploaded to the intra, without uploading them
+    ///
+    /// Useful to inspect or log what `save_changes` sends.
+    /// There are two entries for each student (see `export_students` for the format):
+    /// - `items[x][login]`
+    /// - `items[x][present]`
+    ///
+    /// Staff members are not uploaded, see `save_changes_with_staff`.
+    /// Only students whose presence changed since they were fetched or saved are uploaded,
+    /// see `save_all_changes`.
+    /// Students without presence status are not uploaded, see `SaveOptions`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use epitok::event::Event;
+    /// # fn example(event: &Event) {
+    /// for (key, value) in event.save_changes_dry_run() {
+    ///     println!("{}={}", key, value);
+    /// }
+    /// # }
+    /// ```
+    pub fn save_changes_dry_run(&self) -> HashMap<String, String> {
+        self.export_students(&SaveOptions::new())
+    }
+
+    /// Get changes that would be uploaded to the intra with some options, without uploading them
+    ///
+    /// # Arguments
+    ///
+    /// * `options` - Students to upload, see `SaveOptions`
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use epitok::event::Event;
+    /// use epitok::event::SaveOptions;
+    ///
+    /// # fn example(event: &Event) {
+    /// let options = SaveOptions::new().all_students().include_unmarked();
+    /// println!("{} students would be uploaded", event.save_changes_dry_run_with_options(&options).len() / 2);
+    /// # }
+    /// ```
+    pub fn save_changes_dry_run_with_options(
+        &self,
+        options: &SaveOptions,
+    ) -> HashMap<String, String> {
+        self.export_students(options)
+    }
+
+    /// Get staff members (assistants and teachers) registered to the event
+    pub fn staff(&self) -> Vec<&Student> {
+        self.students
+            .iter()
+            .filter(|student| student.is_staff())
+            .collect()
+    }
+
+    /// Check presence statuses before uploading them
+    ///
+    /// Saving an event where no student has a presence status would remove
+    /// the presences already saved on the intra.
+    /// This check is done by `save_changes`.
+    pub fn validate_presence_data(&self) -> Result<(), ValidationError> {
+        if self.students.is_empty() {
+            return Err(ValidationError::NoStudents);
+        }
+
+        if self
+            .students
+            .iter()
+            .filter(|s| !s.is_staff())
+            .all(|s| *s.get_presence() == Presence::None)
+        {
+            return Err(ValidationError::AllNone);
+        }
+
+        Ok(())
+    }
+
+    /// Save changes to the intra (upload them)
+    ///
+    /// Only students whose presence changed since they were fetched or saved are uploaded,
+    /// so saving twice in a row does nothing the second time.
+    /// Use `save_all_changes` to upload every student.
+    /// Students without presence status are not uploaded, see `save_changes_with_options`.
+    ///
+    /// Staff members are not uploaded, use `save_changes_with_staff` to upload them too.
+    ///
+    /// # Arguments
+    ///
+    /// * `autologin` - Autologin link. If you use the `epitok::auth::Auth` struct, use its `get_autologin` method
+    ///
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, fields(event = %self.code), err)
+    )]
+    pub async fn save_changes(&mut self, autologin: &str) -> Result<(), Box<dyn error::Error>> {
+        self.upload_students(intra::IntraClient::shared(), autologin, &SaveOptions::new())
+            .await
+    }
+
+    /// Save presences of all students to the intra, even the ones that did not change
+    ///
+    /// Students without presence status are uploaded too, which resets their presence on the intra.
+    /// Staff members are not uploaded.
+    ///
+    /// # Arguments
+    ///
+    /// * `autologin` - Autologin link. If you use the `epitok::auth::Auth` struct, use its `get_autologin` method
+    pub async fn save_all_changes(&mut self, autologin: &str) -> Result<(), Box<dyn error::Error>> {
+        let options = SaveOptions::new().all_students().include_unmarked();
+        self.upload_students(intra::IntraClient::shared(), autologin, &options)
+            .await
+    }
+
+    /// Save changes to the intra (upload them), including the presences of staff members
+    ///
+    /// # Arguments
+    ///
+    /// * `autologin` - Autologin link. If you use the `epitok::auth::Auth` struct, use its `get_autologin` method
+    pub async fn save_changes_with_staff(
+        &mut self,
+        autologin: &str,
+    ) -> Result<(), Box<dyn error::Error>> {
+        self.upload_students(
+            intra::IntraClient::shared(),
+            autologin,
+            &SaveOptions::new().include_staff(),
+        )
+        .await
+    }
+
+    /// Save changes to the intra (upload them), choosing which students are uploaded
+    ///
+    /// # Arguments
+    ///
+    /// * `autologin` - Autologin link. If you use the `epitok::auth::Auth` struct, use its `get_autologin` method
+    /// * `options` - Students to upload, see `SaveOptions`
+    pub async fn save_changes_with_options(
+        &mut self,
+        autologin: &str,
+        options: &SaveOptions,
+    ) -> Result<(), Box<dyn error::Error>> {
+        self.upload_students(intra::IntraClient::shared(), autologin, options)
+            .await
+    }
+
+    /// Save changes to the intra, then check the intra kept them
+    ///
+    /// The intra sometimes accepts an upload but drops some of its entries.
+    /// Students uploaded by `save_changes` are fetched again and compared with the presences sent,
+    /// students that were not uploaded are ignored.
+    ///
+    /// Mismatched students are modified again, the next save uploads them.
+    ///
+    /// # Arguments
+    ///
+    /// * `autologin` - Autologin link. If you use the `epitok::auth::Auth` struct, use its `get_autologin` method
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use epitok::event::get_event_by_path;
+    ///
+    /// # #[async_std::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let autologin = "https://intra.epitech.eu/auth-abcdefghijklmnopqrstuvwxyz1234567890abcd";
+    /// let path = "/module/2020/B-CPE-110/PAR-1-1/acti-123456/event-654321";
+    ///
+    /// let mut event = get_event_by_path(autologin, path).await?;
+    /// event.fetch_students(autologin).await?;
+    /// event.set_student_present("first.last@epitech.eu")?;
+    ///
+    /// let report = event.save_and_verify(autologin).await?;
+    /// for (login, expected, actual) in &report.mismatched {
+    ///     println!("{}: sent {:?}, intra has {:?}", login, expected, actual);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn save_and_verify(
+        &mut self,
+        autologin: &str,
+    ) -> Result<VerifyReport, Box<dyn error::Error>> {
+        self.upload_and_verify(intra::IntraClient::shared(), autologin)
+            .await
+    }
+
+    /// Upload presences of students with a client, then compare them with the ones of the intra
+    async fn upload_and_verify(
+        &mut self,
+        client: &intra::IntraClient,
+        autologin: &str,
+    ) -> Result<VerifyReport, Box<dyn error::Error>> {
+        let options = SaveOptions::new();
+
+        // presences that will be uploaded
+        let expected: Vec<(String, Presence)> = self
+            .students
+            .iter()
+            .filter(|student| options.keeps(student))
+            .map(|student| (student.get_login().to_string(), *student.get_presence()))
+            .collect();
+
+        self.upload_students(client, autologin, &options).await?;
+        if expected.is_empty() {
+            return Ok(VerifyReport::default());
+        }
+
+        let remote = students_for_with_client(client, autologin, &self.code()).await?;
+        let report = VerifyReport::compare(&expected, &remote);
+
+        // the intra does not have these presences, upload them again on next save
+        for (login, _, actual) in &report.mismatched {
+            if let Some(student) = self
+                .students
+                .iter_mut()
+                .find(|student| student.get_login().eq_ignore_ascii_case(login))
+            {
+                student.set_saved_presence(*actual);
+            }
+        }
+
+        Ok(report)
+    }
+
+    /// Upload presences of registered students
+    async fn upload_students(
+        &mut self,
+        client: &intra::IntraClient,
+        autologin: &str,
+        options: &SaveOptions,
+    ) -> Result<(), Box<dyn error::Error>> {
+        // presences of appointments are set with slots
+        if self.appointment {
+            return Err(Error::Appointment.into());
+        }
+
+        // do not reset presences on the intra
+        self.validate_presence_data()?;
+
+        // export students to intra format
+        let students = self.export_students(options);
+
+        // nothing changed since last save
+        if students.is_empty() {
+            return Ok(());
+        }
+
+        // upload and check intra reply
+        client
+            .update_presences(autologin, self.code().as_str(), students)
+            .await?;
+
+        // uploaded presences are now the ones saved on the intra
+        self.students
+            .iter_mut()
+            .filter(|student| options.keeps(student))
+            .for_each(Student::mark_saved);
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// # Activity kind
+///
+/// Type of the activity an event belongs to
+pub enum ActivityKind {
+    /// Practical work
+    Tp,
+    /// Tutorial
+    Td,
+    /// Project
+    Project,
+    /// Exam
+    Exam,
+    /// Follow-up (appointment)
+    FollowUp,
+    /// Any other type, with its intra code
+    Other(String),
+}
+
+/// Unknown type, with an empty intra code
+impl Default for ActivityKind {
+    fn default() -> Self {
+        ActivityKind::Other(String::new())
+    }
+}
+
+impl ActivityKind {
+    /// Get activity type from its intra code
+    ///
+    /// Unknown codes are kept:
+    ///
+    /// ```
+    /// use epitok::event::ActivityKind;
+    ///
+    /// assert_eq!(ActivityKind::from("exam"), ActivityKind::Exam);
+    /// assert_eq!(ActivityKind::from("kickoff").code(), "kickoff");
+    /// ```
+    pub fn from(code: &str) -> Self {
+        match code {
+            "tp" => ActivityKind::Tp,
+            "td" => ActivityKind::Td,
+            "proj" => ActivityKind::Project,
+            "exam" => ActivityKind::Exam,
+            "rdv" => ActivityKind::FollowUp,
+            _ => ActivityKind::Other(code.to_string()),
+        }
+    }
+
+    /// Code of activity type on the intra
+    pub fn code(&self) -> &str {
+        match self {
+            ActivityKind::Tp => "tp",
+            ActivityKind::Td => "td",
+            ActivityKind::Project => "proj",
+            ActivityKind::Exam => "exam",
+            ActivityKind::FollowUp => "rdv",
+            ActivityKind::Other(code) => code,
+        }
+    }
+}
+
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// # Slot
+///
+/// Time slot of an appointment, and the students registered to it
+pub struct Slot {
+    /// Id of slot
+    id: Option<u64>,
+    /// When slot starts
+    time: chrono::NaiveDateTime,
+    /// Registered students (one student, or the members of a group)
+    pub members: Vec<Student>,
+}
+
+impl Slot {
+    /// Get id
+    pub fn id(&self) -> Option<u64> {
+        self.id
+    }
+
+    /// Get date and time when slot starts
+    pub fn time(&self) -> &chrono::NaiveDateTime {
+        &self.time
+    }
+
+    /// Get mutable list of registered students
+    pub fn members(&mut self) -> &mut Vec<Student> {
+        &mut self.members
+    }
+
+    /// Set presence of all registered students
+    pub fn set_presence(&mut self, presence: Presence) {
+        for student in self.members.iter_mut() {
+            student.set_presence(presence);
+        }
+    }
+
+    /// Set presence of a registered student
+    ///
+    /// # Arguments
+    ///
+    /// * `login` - Student email address
+    /// * `presence` - Type of presence to set
+    pub fn set_member_presence(&mut self, login: &str, presence: Presence) -> Result<(), Error> {
+        match self.members.iter_mut().find(|s| s.get_login() == login) {
+            Some(student) => {
+                student.set_presence(presence);
+                Ok(())
+            }
+            None => Err(Error::StudentNotFound(login.to_string())),
+        }
+    }
+}
+
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// Room of an event
+pub struct Room {
+    code: String,
+    seats: Option<u32>,
+}
+
+impl Room {
+    /// Full code of room
+    /// # Output format
+    /// `FR/PAR/Voltaire/Amphi-A`
+    pub fn code(&self) -> &str {
+        &self.code
+    }
+
+    /// Short name of room
+    /// # Output format
+    /// `Amphi A`
+    pub fn name(&self) -> String {
+        match self.code.rsplit('/').next() {
+            Some(name) => name.replace('-', " "),
+            None => self.code.clone(),
+        }
+    }
+
+    /// Number of seats in room
+    pub fn seats(&self) -> Option<u32> {
+        self.seats
+    }
+}
+
+/// Events are equal if they have the same code, whatever their title or time
+impl PartialEq for Event {
+    fn eq(&self, other: &Self) -> bool {
+        self.code == other.code
+    }
+}
+
+impl Eq for Event {}
+
+/// Summary of event
+///
+/// # Output format
+///
+/// `[HH:MM–HH:MM] Title (Module)`
+impl fmt::Display for Event {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "[{}–{}] {} ({})",
+            self.start_time_str(),
+            self.end_time_str(),
+            self.title,
+            self.module
+        )
+    }
+}
+
+#[derive(Debug)]
+/// Difference of presence of a student between two events
+pub struct PresenceDiff {
+    /// Student email address
+    pub login: String,
+    /// Presence in the first event, `None` if the student is not registered to it
+    pub before: Option<Presence>,
+    /// Presence in the second event, `None` if the student is not registered to it
+    pub after: Option<Presence>,
+}
+
+#[derive(Debug)]
+/// # Activity
+///
+/// Information about an activity of a module, with its events
+pub struct Activity {
+    /// Name of the activity
+    title: String,
+    /// Type of activity
+    kind: ActivityKind,
+    /// Type of activity, as displayed on the intra
+    kind_label: String,
+    /// Description of the activity
+    description: String,
+    /// Path of the project page, if the activity is a project
+    project: Option<String>,
+    /// Events of the activity, without their registered students
+    pub events: Vec<Event>,
+}
+
+impl Activity {
+    /// Get name
+    pub fn title(&self) -> &str {
+        &self.title
+    }
+
+    /// Get type of activity
+    pub fn kind(&self) -> &ActivityKind {
+        &self.kind
+    }
+
+    /// Get type of activity, as displayed on the intra
+    pub fn kind_label(&self) -> &str {
+        &self.kind_label
+    }
+
+    /// Get description
+    pub fn description(&self) -> &str {
+        &self.description
+    }
+
+    /// Get URL to the project page, if the activity is a project
+    ///
+    /// # Output format
+    ///
+    /// `/module/2019/X-XXX-000/XXX-0-0/acti-000000/project`
+    pub fn project(&self) -> Option<&str> {
+        self.project.as_deref()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// Project an activity is linked to
+pub struct ProjectRef {
+    /// Path of the project page, `/module/2019/X-XXX-000/XXX-0-0/acti-000000/project`
+    pub code: String,
+    /// Name of the project
+    pub title: String,
+}
+
+#[derive(Debug, Clone)]
+/// Session of an activity, an activity being made of one or several events
+pub struct SessionRef {
+    /// Code of the event of the session
+    pub code: Code,
+    /// When session starts
+    pub start: chrono::NaiveDateTime,
+}
+
+#[cfg(feature = "csv")]
+#[derive(Debug, Default)]
+/// Result of a CSV import
+pub struct CsvImport {
+    /// Number of students whose presence has been updated
+    pub updated: usize,
+    /// Logins that are not registered to the event
+    pub unknown: Vec<String>,
+    /// Logins with a presence status that can't be read, and this status
+    pub invalid: Vec<(String, String)>,
+}
+
+#[derive(Debug, Default)]
+/// Events read from the planning, with the entries that could not be read
+pub struct EventListing {
+    /// Events that have been read
+    pub events: Vec<Event>,
+    /// Position in the planning of entries that could not be read, and why
+    pub skipped: Vec<(usize, Error)>,
+}
+
+#[derive(Debug, Default)]
+/// Result of a listing of events the user can mark
+pub struct MarkableListing {
+    /// Number of events the user can mark, stored in the list
+    pub markable: usize,
+    /// Number of events removed because the user can't mark them
+    pub filtered: usize,
+    /// Number of events removed because the rights of the user could not be checked
+    pub failed: usize,
+}
+
+#[derive(Debug, Clone, Default)]
+/// # Event filter
+///
+/// Criteria to narrow a list of events
+///
+/// All criteria must match for an event to be kept,
+/// an empty filter matches every event.
+///
+/// # Example
+///
+/// ```
+/// use epitok::event::{ActivityKind, EventFilter};
+///
+/// let filter = EventFilter::new()
+///     .module("B-CPE-100")
+///     .title("bootstrap")
+///     .kind(ActivityKind::Tp);
+/// ```
+pub struct EventFilter {
+    /// Code of module
+    module: Option<String>,
+    /// Code of instance of module
+    instance: Option<String>,
+    /// Scholar year
+    year: Option<String>,
+    /// Part of title, lowercase
+    title: Option<String>,
+    /// Type of activity
+    kind: Option<ActivityKind>,
+    /// Earliest start time
+    start_after: Option<chrono::NaiveTime>,
+    /// Latest start time
+    start_before: Option<chrono::NaiveTime>,
+}
+
+impl EventFilter {
+    /// Create a filter matching every event
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Keep events of a module
+    ///
+    /// # Arguments
+    ///
+    /// * `code` - Code of module, such as `X-XXX-000`
+    pub fn module(mut self, code: &str) -> Self {
+        self.module = Some(code.to_string());
+        self
+    }
+
+    /// Keep events of an instance of module (campus or group), ignoring case
+    ///
+    /// # Arguments
+    ///
+    /// * `code` - Code of instance, such as `XXX-0-0`
+    pub fn instance(mut self, code: &str) -> Self {
+        self.instance = Some(code.to_string());
+        self
+    }
+
+    /// Keep events of a scholar year
+    ///
+    /// # Arguments
+    ///
+    /// * `year` - Scholar year, such as `2020`, see `current_scolar_year`
+    pub fn year(mut self, year: &str) -> Self {
+        self.year = Some(year.to_string());
+        self
+    }
+
+    /// Keep events whose title contains some text, ignoring case
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - Text to look for in the title
+    pub fn title(mut self, text: &str) -> Self {
+        self.title = Some(text.to_lowercase());
+        self
+    }
+
+    /// Keep events of a type of activity
+    pub fn kind(mut self, kind: ActivityKind) -> Self {
+        self.kind = Some(kind);
+        self
+    }
+
+    /// Keep events starting within a time window, bounds included
+    ///
+    /// # Arguments
+    ///
+    /// * `from` - Earliest start time
+    /// * `to` - Latest start time
+    pub fn start_between(mut self, from: chrono::NaiveTime, to: chrono::NaiveTime) -> Self {
+        self.start_after = Some(from);
+        self.start_before = Some(to);
+        self
+    }
+
+    /// Check if an event matches all criteria
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use epitok::event::Event;
+    /// use epitok::event::EventFilter;
+    ///
+    /// # fn example(event: &Event) {
+    /// if EventFilter::new().instance("PAR-1-1").matches(event) {
+    ///     println!("{} is in Paris", event);
+    /// }
+    /// # }
+    /// ```
+    pub fn matches(&self, event: &Event) -> bool {
+        if !self.matches_code(&event.code) {
+            return false;
+        }
+        if let Some(title) = &self.title {
+            if !event.title.to_lowercase().contains(title.as_str()) {
+                return false;
+            }
+        }
+        if let Some(kind) = &self.kind {
+            if &event.kind != kind {
+                return false;
+            }
+        }
+        if let Some(from) = self.start_after {
+            if event.start_time() < from {
+                return false;
+            }
+        }
+        if let Some(to) = self.start_before {
+            if event.start_time() > to {
+                return false;
+            }
+        }
+        true
+    }
+
+    /// Remove events that do not match from a list
+    pub fn apply(&self, events: &mut Vec<Event>) {
+        events.retain(|event| self.matches(event));
+    }
+
+    /// Check if the code of an event matches the module, instance and year criteria
+    fn matches_code(&self, code: &Code) -> bool {
+        if let Some(module) = &self.module {
+            if code.module() != module {
+                return false;
+            }
+        }
+        if let Some(instance) = &self.instance {
+            if !code.instance().eq_ignore_ascii_case(instance) {
+                return false;
+            }
+        }
+        if let Some(year) = &self.year {
+            if code.year() != year {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+/// # Listing options
+///
+/// Options applied while events are retrieved from the planning
+///
+/// # Example
+///
+/// ```
+/// use epitok::event::ListOptions;
+///
+/// let options = ListOptions::new().location("FR/PAR").instance("PAR-1-1");
+/// ```
+pub struct ListOptions {
+    /// Location the planning is restricted to
+    location: Option<String>,
+    /// Criteria events must match
+    filter: EventFilter,
+}
+
+impl ListOptions {
+    /// Create options keeping every event
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Keep only events of an instance (campus or group), ignoring case
+    ///
+    /// Events without an instance are skipped.
+    ///
+    /// # Arguments
+    ///
+    /// * `code` - Code of instance, such as `PAR-1-1`
+    pub fn instance(mut self, code: &str) -> Self {
+        self.filter = self.filter.instance(code);
+        self
+    }
+
+    /// Ask the planning for events of a location only
+    ///
+    /// Filtering is done by the intra, which is faster than filtering events afterwards.
+    ///
+    /// # Arguments
+    ///
+    /// * `location` - Code of location, such as `FR/PAR`
+    pub fn location(mut self, location: &str) -> Self {
+        self.location = Some(location.to_string());
+        self
+    }
+
+    /// Keep only events of a scholar year
+    ///
+    /// # Arguments
+    ///
+    /// * `year` - Scholar year, such as `2020`, see `current_scolar_year`
+    pub fn year(mut self, year: &str) -> Self {
+        self.filter = self.filter.year(year);
+        self
+    }
+
+    /// Keep only events matching a filter
+    ///
+    /// Replaces the instance and year set before.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use epitok::event::{EventFilter, ListOptions};
+    ///
+    /// let options = ListOptions::new().filter(EventFilter::new().instance("PAR-2-1").title("boot"));
+    /// ```
+    pub fn filter(mut self, filter: EventFilter) -> Self {
+        self.filter = filter;
+        self
+    }
+
+    /// Add parameters of the planning request to its URL
+    fn append_query(&self, url: &mut reqwest::Url) {
+        if let Some(location) = &self.location {
+            // the location is URL-encoded, its slashes included
+            url.query_pairs_mut().append_pair("location", location);
+        }
+    }
+
+    /// Check if an entry of the planning should be kept
+    ///
+    /// Entries are checked by code before being read, so entries of other instances are not reported when malformed.
+    fn keeps(&self, event: &serde_json::Value) -> bool {
+        let filter = &self.filter;
+
+        match construct_code(event) {
+            Some(code) => filter.matches_code(&code),
+            // entries without a code can't match criteria on their code
+            None => filter.module.is_none() && filter.instance.is_none() && filter.year.is_none(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+/// # Saving options
+///
+/// Options choosing which students are uploaded when saving an event
+///
+/// By default, only students whose presence changed since they were fetched or saved are uploaded,
+/// without staff members and without students left at `Presence::None`.
+///
+/// Unmarked students would be uploaded with an empty presence,
+/// erasing presences entered on the intra in the meantime (with tokens for example).
+/// A student whose presence was reset to `Presence::None` is modified,
+/// but is still skipped unless `include_unmarked` is set,
+/// and stays modified until it is uploaded.
+///
+/// # Example
+///
+/// ```
+/// use epitok::event::SaveOptions;
+///
+/// let options = SaveOptions::new().all_students().include_unmarked();
+/// ```
+pub struct SaveOptions {
+    /// Upload staff members too
+    include_staff: bool,
+    /// Upload students without presence status
+    include_unmarked: bool,
+    /// Upload students whose presence did not change
+    all_students: bool,
+}
+
+impl SaveOptions {
+    /// Create options uploading modified and marked students only
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Upload presences of staff members (assistants and teachers) too
+    pub fn include_staff(mut self) -> Self {
+        self.include_staff = true;
+        self
+    }
+
+    /// Upload students with `Presence::None`, which resets their presence on the intra
+    pub fn include_unmarked(mut self) -> Self {
+        self.include_unmarked = true;
+        self
+    }
+
+    /// Upload every student, even the ones whose presence did not change
+    pub fn all_students(mut self) -> Self {
+        self.all_students = true;
+        self
+    }
+
+    /// Check if a student should be uploaded
+    fn keeps(&self, student: &Student) -> bool {
+        if !self.include_staff && student.is_staff() {
+            return false;
+        }
+
+        if !self.include_unmarked && *student.get_presence() == Presence::None {
+            return false;
+        }
+
+        self.all_students || student.is_modified()
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+/// # Verification report
+///
+/// Presences uploaded to the intra compared with the ones it kept, see `Event::save_and_verify`
+pub struct VerifyReport {
+    /// Number of students whose presence was kept by the intra
+    pub confirmed: usize,
+    /// Students whose presence was not kept: login, uploaded presence and presence on the intra
+    pub mismatched: Vec<(String, Presence, Presence)>,
+}
+
+impl VerifyReport {
+    /// Compare uploaded presences with students fetched from the intra
+    ///
+    /// Logins are compared ignoring case, presences are compared as uploaded:
+    /// `Presence::Late` is confirmed by `Presence::Present`.
+    /// Students missing from the intra have `Presence::None`.
+    ///
+    /// # Arguments
+    ///
+    /// * `expected` - Logins and presences that were uploaded
+    /// * `remote` - Students registered to the event on the intra
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use epitok::event::VerifyReport;
+    /// # use epitok::student::{Presence, Student};
+    /// # fn example(remote: &[Student]) {
+    /// let expected = vec![("first.last@epitech.eu".to_string(), Presence::Present)];
+    /// let report = VerifyReport::compare(&expected, remote);
+    /// if !report.is_confirmed() {
+    ///     println!("{} presences differ", report.mismatched.len());
+    /// }
+    /// # }
+    /// ```
+    pub fn compare(expected: &[(String, Presence)], remote: &[Student]) -> Self {
+        let remote: HashMap<String, Presence> = remote
+            .iter()
+            .map(|student| (student.get_login().to_lowercase(), *student.get_presence()))
+            .collect();
+
+        let mut report = Self::default();
+        for (login, presence) in expected {
+            let actual = remote
+                .get(&login.to_lowercase())
+                .copied()
+                .unwrap_or(Presence::None);
+
+            // presences the intra does not know are uploaded as another one
+            if actual.intra_value() == presence.intra_value() {
+                report.confirmed += 1;
+            } else {
+                report.mismatched.push((login.clone(), *presence, actual));
+            }
+        }
+        report
+    }
+
+    /// Check if the intra kept every uploaded presence
+    pub fn is_confirmed(&self) -> bool {
+        self.mismatched.is_empty()
+    }
+}
+
+#[derive(Debug)]
+/// Error possibilities
+pub enum Error {
+    /// Event does not have a URL
+    EventURL,
+    /// Event does not have a title
+    Title,
+    /// Event is not linked to any modules
+    Module,
+    /// Event does not have a starting time
+    TimeStart,
+    /// Event does not have a finish time
+    TimeEnd,
+    /// Student is not registered to the event
+    StudentNotFound(String),
+    /// Start date is after end date
+    DateRange,
+    /// Event is an appointment, presences are set with its slots
+    Appointment,
+    /// Slot of an appointment does not have a date
+    SlotDate,
+    /// Date argument is neither a relative date nor a date in `YYYY-MM-DD` format
+    DateArg(String),
+}
+
+impl error::Error for Error {}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let message = match self {
+            Error::EventURL => "This event doesn't have a url".into(),
+            Error::Title => "This event does not have a title".into(),
+            Error::Module => "This event does not belong to a module".into(),
+            Error::TimeStart => "This event does not have a starting time".into(),
+            Error::TimeEnd => "This event does not have a finish time".into(),
+            Error::DateRange => "The start date is after the end date".into(),
+            Error::Appointment => "This event is an appointment, use its slots".into(),
+            Error::SlotDate => "This appointment slot does not have a date".into(),
+            Error::DateArg(input) => format!("Invalid date: {}", input),
+            Error::StudentNotFound(login) => {
+                format!("Student {} is not registered to this event", login)
+            }
+        };
+        write!(f, "{}", message)
+    }
+}
+
+#[derive(Debug)]
+/// Validation error possibilities, before uploading presences
+pub enum ValidationError {
+    /// Event does not have any students
+    NoStudents,
+    /// No student has a presence status
+    AllNone,
+}
+
+impl error::Error for ValidationError {}
+
+impl fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let message = match *self {
+            ValidationError::NoStudents => "This event does not have any students",
+            ValidationError::AllNone => "No student has a presence status",
+        };
+        write!(f, "{}", message)
+    }
+}
+
+#[derive(Debug)]
+/// Error possibilities when reading a code
+pub enum CodeError {
+    /// Code is not made of `/module/` followed by five segments
+    Format,
+    /// A component of the code is malformed, see `Code::validate`
+    Invalid {
+        /// Name of the component: `year`, `module`, `instance`, `acti` or `event`
+        field: &'static str,
+        /// Value of the component
+        value: String,
+    },
+}
+
+impl error::Error for CodeError {}
+
+impl fmt::Display for CodeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let message = match self {
+            CodeError::Format => {
+                "Code must look like /module/YEAR/MODULE/INSTANCE/acti-XXX/event-XXX".into()
+            }
+            CodeError::Invalid { field, value } => format!("Invalid {} in code: {}", field, value),
+        };
+        write!(f, "{}", message)
+    }
+}
+
+#[derive(Debug)]
+/// Time representation
+enum Time {
+    /// Event start
+    Start,
+    /// Event end
+    End,
+}
+
+/// Parse start or end date and time from JSON
+fn parse_time(json: &serde_json::Value, time: Time) -> Option<chrono::NaiveDateTime> {
+    let time = match time {
+        Time::Start => "start",
+        Time::End => "end",
+    };
+
+    match json[time].as_str() {
+        Some(start) => chrono::NaiveDateTime::parse_from_str(start, "%Y-%m-%d %H:%M:%S").ok(),
+        None => None,
+    }
+}
+
+/// Build a slot from an entry of an appointment
+fn parse_slot(slot: &serde_json::Value) -> Result<Slot, Box<dyn error::Error>> {
+    let time = match slot["date"].as_str() {
+        Some(date) => chrono::NaiveDateTime::parse_from_str(date, "%Y-%m-%d %H:%M:%S")?,
+        None => return Err(Error::SlotDate.into()),
+    };
+
+    let mut members = Vec::new();
+    match slot["members"].as_array() {
+        Some(list) => {
+            for member in list {
+                members.push(parse_student(member)?);
+            }
+        }
+        // slot of a single student
+        None if slot["master"].is_object() => members.push(parse_student(&slot["master"])?),
+        None => (),
+    }
+
+    Ok(Slot {
+        id: intra::parse_value(&slot["id"]),
+        time,
+        members,
+    })
+}
+
+/// Parse description of an activity from JSON
+fn parse_description(json: &serde_json::Value) -> Option<String> {
+    match json["description"].as_str() {
+        Some(description) if !description.trim().is_empty() => Some(description.to_string()),
+        _ => None,
+    }
+}
+
+/// Parse project an activity is linked to from JSON
+///
+/// The project title is the title of the activity if the intra does not send it.
+fn parse_project(json: &serde_json::Value, code: &Code) -> Option<ProjectRef> {
+    let title = match (json["project_title"].as_str(), json["is_projet"].as_bool()) {
+        (Some(title), _) if !title.is_empty() => title,
+        (_, Some(true)) => json["title"]
+            .as_str()
+            .or_else(|| json["acti_title"].as_str())?,
+        _ => return None,
+    };
+
+    Some(ProjectRef {
+        code: format!(
+            "/module/{}/{}/{}/{}/project",
+            code.year, code.module, code.instance, code.acti
+        ),
+        title: title.to_string(),
+    })
+}
+
+/// Get logins of students, in lowercase
+fn lowercase_logins(students: &[Student]) -> HashSet<String> {
+    students
+        .iter()
+        .map(|student| student.get_login().to_lowercase())
+        .collect()
+}
+
+/// Parse room of event from JSON
+fn parse_room(json: &serde_json::Value) -> Option<Room> {
+    let code = json["room"]["code"].as_str()?.to_string();
+
+    let seats = intra::parse_value(&json["room"]["seats"]);
+
+    Some(Room { code, seats })
+}
+
+/// Gather code elements making event intra information
+fn construct_code(json: &serde_json::Value) -> Option<Code> {
+    Code {
+        year: json["scolaryear"].as_str()?.into(),
+        module: json["codemodule"].as_str()?.into(),
+        instance: json["codeinstance"].as_str()?.into(),
+        acti: json["codeacti"].as_str()?.into(),
+        event: json["codeevent"].as_str()?.into(),
+    }
+    .into()
+}
+
+/// Show events of a particular date
+///
+/// # Arguments
+///
+/// * `list` - Where events will be stored
+/// * `autologin` - User autologin link
+/// * `date` - Day of events
+///
+/// # Return value
+/// On success the number of retrieved events will be returned.
+///
+/// On failure the error type will be returned
+///
+/// # Example
+///
+/// Get a vector of events from a particular date and print their name
+///
+/// ```no_run
+/// use chrono::NaiveDate;
+/// use epitok::event::{Event, list_events_on};
+///
+/// # #[async_std::main]
+/// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let date = NaiveDate::from_ymd_opt(2020, 7, 1).unwrap();
+/// let autologin = "https://intra.epitech.eu/auth-abcdefghijklmnopqrstuvwxyz1234567890abcd";
+/// let mut events: Vec<Event> = Vec::new();
+///
+/// list_events_on(&mut events, &autologin, date).await?;
+/// for event in events {
+///     println!("event: {} - {}", event.title(), event.module());
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub async fn list_events_on(
+    list: &mut Vec<Event>,
+    autologin: &str,
+    date: chrono::NaiveDate,
+) -> Result<usize, Box<dyn error::Error>> {
+    list_events_range_on(list, autologin, date, date).await
+}
+
+/// Show events of a particular date
+///
+/// Same as `list_events_on`, with the date as a string.
+///
+/// # Arguments
+///
+/// * `list` - Where events will be stored
+/// * `autologin` - User autologin link
+/// * `raw_date` - Date in `YYYY-MM-DD` format
+///
+/// # Return value
+/// On success the number of retrieved events will be returned.
+///
+/// On failure the error type will be returned
+pub async fn list_events(
+    list: &mut Vec<Event>,
+    autologin: &str,
+    raw_date: &str,
+) -> Result<usize, Box<dyn error::Error>> {
+    let date = chrono::NaiveDate::parse_from_str(raw_date, "%Y-%m-%d")?;
+
+    list_events_on(list, autologin, date).await
+}
+
+/// Show events of a particular date that match a filter
+///
+/// # Arguments
+///
+/// * `list` - Where events will be stored
+/// * `autologin` - User autologin link
+/// * `raw_date` - Date in `YYYY-MM-DD` format
+/// * `filter` - Criteria events must match
+///
+/// # Return value
+/// On success the number of matching events will be returned.
+///
+/// On failure the error type will be returned
+pub async fn list_events_filtered(
+    list: &mut Vec<Event>,
+    autologin: &str,
+    raw_date: &str,
+    filter: &EventFilter,
+) -> Result<usize, Box<dyn error::Error>> {
+    list_events(list, autologin, raw_date).await?;
+    filter.apply(list);
+    Ok(list.len())
+}
+
+/// Show events of a particular date, with listing options
+///
+/// # Arguments
+///
+/// * `list` - Where events will be stored
+/// * `autologin` - User autologin link
+/// * `raw_date` - Date in `YYYY-MM-DD` format
+/// * `options` - Options applied to the planning
+///
+/// # Return value
+/// On success the number of retrieved events will be returned.
+///
+/// On failure the error type will be returned
+pub async fn list_events_with(
+    list: &mut Vec<Event>,
+    autologin: &str,
+    raw_date: &str,
+    options: &ListOptions,
+) -> Result<usize, Box<dyn error::Error>> {
+    list_events_range_with(list, autologin, raw_date, raw_date, options).await
+}
+
+/// Show events between two dates
+///
+/// # Arguments
+///
+/// * `list` - Where events will be stored
+/// * `autologin` - User autologin link
+/// * `start` - First date
+/// * `end` - Last date (included), can't be before `start`
+///
+/// # Return value
+/// On success the number of retrieved events will be returned.
+/// Events are sorted by start time, and an event appearing on several days is only stored once.
+/// The day of each event can be found with its `date` method.
+///
+/// On failure the error type will be returned
+///
+/// # Example
+///
+/// Get events of a weekend
+///
+/// ```no_run
+/// use chrono::NaiveDate;
+/// use epitok::event::{Event, list_events_range_on};
+///
+/// # #[async_std::main]
+/// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let autologin = "https://intra.epitech.eu/auth-abcdefghijklmnopqrstuvwxyz1234567890abcd";
+/// let mut events: Vec<Event> = Vec::new();
+/// let saturday = NaiveDate::from_ymd_opt(2020, 7, 4).unwrap();
+/// let sunday = NaiveDate::from_ymd_opt(2020, 7, 5).unwrap();
+///
+/// list_events_range_on(&mut events, &autologin, saturday, sunday).await?;
+/// for event in events {
+///     println!("event: {} - {}", event.title(), event.module());
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub async fn list_events_range_on(
+    list: &mut Vec<Event>,
+    autologin: &str,
+    start: chrono::NaiveDate,
+    end: chrono::NaiveDate,
+) -> Result<usize, Box<dyn error::Error>> {
+    list_events_range_on_with_client(intra::IntraClient::shared(), list, autologin, start, end)
+        .await
+}
+
+/// Get events between two dates, with a client instead of the shared one
+///
+/// See `list_events_range_on`.
+///
+/// # Arguments
+///
+/// * `client` - Client requests are made with
+/// * `list` - Vector of events to write to
+/// * `autologin` - User autologin link
+/// * `start` - First date of events
+/// * `end` - Last date of events
+///
+/// # Example
+///
+/// ```no_run
+/// use chrono::NaiveDate;
+/// use epitok::event::list_events_range_on_with_client;
+/// use epitok::intra::{IntraClient, IntraConfig};
+///
+/// # #[async_std::main]
+/// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let autologin = "https://intra.epitech.eu/auth-abcdefghijklmnopqrstuvwxyz1234567890abcd";
+/// let client = IntraClient::with_config(IntraConfig {
+///     max_retries: 0,
+///     ..IntraConfig::default()
+/// });
+/// let date = NaiveDate::from_ymd_opt(2020, 7, 1).unwrap();
+///
+/// let mut events = Vec::new();
+/// list_events_range_on_with_client(&client, &mut events, autologin, date, date).await?;
+/// # Ok(())
+/// # }
+/// ```
+pub async fn list_events_range_on_with_client(
+    client: &intra::IntraClient,
+    list: &mut Vec<Event>,
+    autologin: &str,
+    start: chrono::NaiveDate,
+    end: chrono::NaiveDate,
+) -> Result<usize, Box<dyn error::Error>> {
+    list_planning(client, list, autologin, start, end, &ListOptions::default()).await
+}
+
+/// Show events between two dates
+///
+/// Same as `list_events_range_on`, with the dates as strings.
+///
+/// # Arguments
+///
+/// * `list` - Where events will be stored
+/// * `autologin` - User autologin link
+/// * `start` - First date in `YYYY-MM-DD` format
+/// * `end` - Last date in `YYYY-MM-DD` format (included), can't be before `start`
+///
+/// # Return value
+/// On success the number of retrieved events will be returned.
+///
+/// On failure the error type will be returned
+///
+/// # Example
+///
+/// Get events of a three-day weekend, with a mocked intranet
+///
+/// ```no_run
+/// use epitok::event::{Event, list_events_range};
+///
+/// # #[async_std::main]
+/// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let autologin = "https://intra.epitech.eu/auth-abcdefghijklmnopqrstuvwxyz1234567890abcd";
+/// let mut events: Vec<Event> = Vec::new();
+///
+/// let count = list_events_range(&mut events, autologin, "2020-07-03", "2020-07-05").await?;
+/// println!("{} events during the weekend", count);
+/// # Ok(())
+/// # }
+/// ```
+pub async fn list_events_range(
+    list: &mut Vec<Event>,
+    autologin: &str,
+    start: &str,
+    end: &str,
+) -> Result<usize, Box<dyn error::Error>> {
+    list_events_range_with(list, autologin, start, end, &ListOptions::default()).await
+}
+
+/// Show events between two dates, with listing options
+///
+/// # Arguments
+///
+/// * `list` - Where events will be stored
+/// * `autologin` - User autologin link
+/// * `start` - First date in `YYYY-MM-DD` format
+/// * `end` - Last date in `YYYY-MM-DD` format (included), can't be before `start`
+/// * `options` - Options applied to the planning
+///
+/// # Return value
+/// On success the number of retrieved events will be returned.
+///
+/// On failure the error type will be returned
+///
+/// # Example
+///
+/// Get events of the Paris campus
+///
+/// ```no_run
+/// use epitok::event::{Event, ListOptions, list_events_range_with};
+///
+/// # #[async_std::main]
+/// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let autologin = "https://intra.epitech.eu/auth-abcdefghijklmnopqrstuvwxyz1234567890abcd";
+/// let mut events: Vec<Event> = Vec::new();
+/// let options = ListOptions::new().instance("PAR-1-1");
+///
+/// list_events_range_with(&mut events, &autologin, "2020-07-03", "2020-07-05", &options).await?;
+/// # Ok(())
+/// # }
+/// ```
+pub async fn list_events_range_with(
+    list: &mut Vec<Event>,
+    autologin: &str,
+    start: &str,
+    end: &str,
+    options: &ListOptions,
+) -> Result<usize, Box<dyn error::Error>> {
+    // check if the dates provided are valid
+    let start = chrono::NaiveDate::parse_from_str(start, "%Y-%m-%d")?;
+    let end = chrono::NaiveDate::parse_from_str(end, "%Y-%m-%d")?;
+
+    list_planning(
+        intra::IntraClient::shared(),
+        list,
+        autologin,
+        start,
+        end,
+        options,
+    )
+    .await
+}
+
+/// Get events of a particular date
+///
+/// # Arguments
+///
+/// * `autologin` - User autologin link
+/// * `date` - Day of events
+///
+/// # Return value
+/// On success the events will be returned, sorted by start time.
+///
+/// On failure the error type will be returned
+///
+/// # Example
+///
+/// ```no_run
+/// use chrono::NaiveDate;
+/// use epitok::event::events_for;
+///
+/// # #[async_std::main]
+/// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let autologin = "https://intra.epitech.eu/auth-abcdefghijklmnopqrstuvwxyz1234567890abcd";
+/// let date = NaiveDate::from_ymd_opt(2020, 7, 1).unwrap();
+///
+/// for event in events_for(autologin, date).await? {
+///     println!("{}", event);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub async fn events_for(
+    autologin: &str,
+    date: chrono::NaiveDate,
+) -> Result<Vec<Event>, Box<dyn error::Error>> {
+    events_between(autologin, date, date).await
+}
+
+/// Get events between two dates
+///
+/// # Arguments
+///
+/// * `autologin` - User autologin link
+/// * `start` - First date
+/// * `end` - Last date (included), can't be before `start`
+///
+/// # Return value
+/// On success the events will be returned, sorted by start time.
+/// An event appearing on several days is only returned once.
+///
+/// On failure the error type will be returned
+pub async fn events_between(
+    autologin: &str,
+    start: chrono::NaiveDate,
+    end: chrono::NaiveDate,
+) -> Result<Vec<Event>, Box<dyn error::Error>> {
+    planning(
+        intra::IntraClient::shared(),
+        autologin,
+        start,
+        end,
+        &ListOptions::default(),
+    )
+    .await
+}
+
+/// Get events between two dates, grouped by day
+///
+/// Events are retrieved with a single request to the planning.
+///
+/// # Arguments
+///
+/// * `autologin` - User autologin link
+/// * `start` - First date
+/// * `end` - Last date (included), can't be before `start`
+///
+/// # Return value
+/// On success the events of each day will be returned, see `group_by_date`.
+///
+/// On failure the error type will be returned
+pub async fn list_events_by_date(
+    autologin: &str,
+    start: chrono::NaiveDate,
+    end: chrono::NaiveDate,
+) -> Result<BTreeMap<chrono::NaiveDate, Vec<Event>>, Box<dyn error::Error>> {
+    let events = events_between(autologin, start, end).await?;
+
+    Ok(group_by_date(events))
+}
+
+/// Group events by their day
+///
+/// Events of each day are sorted by start time, days without events are not in the map.
+///
+/// # Example
+///
+/// ```
+/// use epitok::event::{group_by_date, Event};
+///
+/// # fn example(events: Vec<Event>) {
+/// for (day, events) in group_by_date(events) {
+///     println!("{}: {} events", day, events.len());
+/// }
+/// # }
+/// ```
+pub fn group_by_date(events: Vec<Event>) -> BTreeMap<chrono::NaiveDate, Vec<Event>> {
+    let mut days: BTreeMap<chrono::NaiveDate, Vec<Event>> = BTreeMap::new();
+
+    for event in events {
+        days.entry(event.date()).or_default().push(event);
+    }
+
+    for events in days.values_mut() {
+        sort_events(events);
+    }
+
+    days
+}
+
+/// Show events of a particular date the user can mark presences for
+///
+/// Every event of the date is checked with `Event::can_mark`,
+/// at most `intra::IntraConfig::max_concurrent_requests` events at a time.
+/// Events that could not be checked are left out of the list and counted apart.
+///
+/// # Arguments
+///
+/// * `list` - Where events will be stored
+/// * `autologin` - User autologin link
+/// * `date` - Day of events
+///
+/// # Return value
+/// On success the number of markable events, of filtered out events and of events
+/// that could not be checked will be returned.
+///
+/// On failure the error type will be returned, and the list is left untouched
+pub async fn list_markable_events(
+    list: &mut Vec<Event>,
+    autologin: &str,
+    date: chrono::NaiveDate,
+) -> Result<MarkableListing, Box<dyn error::Error>> {
+    markable_events(intra::IntraClient::shared(), list, autologin, date).await
+}
+
+/// Store events of a particular date the user can mark presences for, with a client
+async fn markable_events(
+    client: &intra::IntraClient,
+    list: &mut Vec<Event>,
+    autologin: &str,
+    date: chrono::NaiveDate,
+) -> Result<MarkableListing, Box<dyn error::Error>> {
+    let events = planning(client, autologin, date, date, &ListOptions::default()).await?;
+
+    let limit = client.config().max_concurrent_requests.max(1);
+    let rights: Vec<Result<bool, intra::Error>> = stream::iter(events.iter())
+        .map(|event| event.can_mark_with_client(client, autologin))
+        .buffered(limit)
+        .collect()
+        .await;
+
+    let mut listing = MarkableListing::default();
+    let mut markable = Vec::new();
+
+    for (event, right) in events.into_iter().zip(rights) {
+        match right {
+            Ok(true) => markable.push(event),
+            Ok(false) => listing.filtered += 1,
+            Err(_) => listing.failed += 1,
+        }
+    }
+
+    *list = markable;
+    listing.markable = list.len();
+
+    Ok(listing)
+}
+
+/// Store events of the planning between two dates in a list
+///
+/// On failure the list is left untouched.
+async fn list_planning(
+    client: &intra::IntraClient,
+    list: &mut Vec<Event>,
+    autologin: &str,
+    start: chrono::NaiveDate,
+    end: chrono::NaiveDate,
+    options: &ListOptions,
+) -> Result<usize, Box<dyn error::Error>> {
+    *list = planning(client, autologin, start, end, options).await?;
+
+    Ok(list.len())
+}
+
+/// Get events of the planning between two dates
+///
+/// Fails on the first entry that can't be read.
+async fn planning(
+    client: &intra::IntraClient,
+    autologin: &str,
+    start: chrono::NaiveDate,
+    end: chrono::NaiveDate,
+    options: &ListOptions,
+) -> Result<Vec<Event>, Box<dyn error::Error>> {
+    let listing = planning_lossy(client, autologin, start, end, options).await?;
+
+    match listing.skipped.into_iter().next() {
+        Some((_, e)) => Err(e.into()),
+        None => Ok(listing.events),
+    }
+}
+
+/// Get events of the planning between two dates, skipping entries that can't be read
+async fn planning_lossy(
+    client: &intra::IntraClient,
+    autologin: &str,
+    start: chrono::NaiveDate,
+    end: chrono::NaiveDate,
+    options: &ListOptions,
+) -> Result<EventListing, Box<dyn error::Error>> {
+    if start > end {
+        return Err(Error::DateRange.into());
+    }
+
+    let mut url = reqwest::Url::parse(&format!(
+        "{}/planning/load?format=json&start={}&end={}",
+        autologin,
+        start.format("%Y-%m-%d"),
+        end.format("%Y-%m-%d")
+    ))?;
+    options.append_query(&mut url);
+
+    let json = match client.get_array_obj(url.as_str()).await {
+        Ok(json) => json,
+        Err(e) => {
+            return match e {
+                intra::Error::Empty => Ok(EventListing::default()), // No events have been retrieved
+                _ => Err(e.into()),                                 // Return the intra error
+            };
+        }
+    };
+
+    Ok(parse_planning(&json, options))
+}
+
+/// Get events of a particular date, skipping entries of the planning that can't be read
+///
+/// Unlike `events_for`, a malformed entry does not make the whole listing fail:
+/// it is recorded in the `skipped` list of the result.
+/// Network and intra errors are still returned as errors.
+///
+/// # Arguments
+///
+/// * `autologin` - User autologin link
+/// * `date` - Day of events
+pub async fn list_events_lossy(
+    autologin: &str,
+    date: chrono::NaiveDate,
+) -> Result<EventListing, Box<dyn error::Error>> {
+    planning_lossy(
+        intra::IntraClient::shared(),
+        autologin,
+        date,
+        date,
+        &ListOptions::default(),
+    )
+    .await
+}
+
+/// Read events from entries of the planning
+///
+/// Entries that can't be read are skipped and recorded with their position in `json`.
+/// Appointments that can't be read are skipped without being recorded.
+/// Events are sorted by start time, and an event appearing on several days is only kept once.
+///
+/// # Arguments
+///
+/// * `json` - Entries of the planning, as returned by the intra
+/// * `options` - Options applied to the planning
+///
+/// # Example
+///
+/// ```
+/// use epitok::event::{parse_planning, ListOptions};
+///
+/// # fn example(json: &[serde_json::Value]) {
+/// let listing = parse_planning(json, &ListOptions::new().instance("PAR-1-1"));
+/// for (position, error) in &listing.skipped {
+///     eprintln!("entry {} skipped: {}", position, error);
+/// }
+/// # }
+/// ```
+pub fn parse_planning(json: &[serde_json::Value], options: &ListOptions) -> EventListing {
+    let mut listing = EventListing::default();
+
+    for (i, event) in json.iter().enumerate() {
+        if !options.keeps(event) {
+            continue;
+        }
+
+        let event = match event["is_rdv"].as_str() {
+            // event with tokens
+            Some("0") => match parse_planning_event(event) {
+                Ok(event) => event,
+                Err(e) => {
+                    listing.skipped.push((i, e));
+                    continue;
+                }
+            },
+            // appointment, skip it if it can't be read
+            Some(_) => match parse_planning_event(event) {
+                Ok(event) => event,
+                Err(_) => continue,
+            },
+            None => continue, // Iterate over next event, skip this one
+        };
+
+        if !options.filter.matches(&event) {
+            continue;
+        }
+
+        listing.events.push(event);
+    }
+
+    // the same event can be returned for multiple days
+    dedup_events(&mut listing.events);
+    sort_events(&mut listing.events);
+
+    listing
+}
+
+/// Remove duplicated events, keeping the first occurrence
+///
+/// Events are duplicates if they have the same code.
+///
+/// # Example
+///
+/// ```
+/// use epitok::event::{dedup_events, Event};
+///
+/// # fn example(mut events: Vec<Event>, other: Vec<Event>) {
+/// events.extend(other);
+/// dedup_events(&mut events);
+/// # }
+/// ```
+pub fn dedup_events(events: &mut Vec<Event>) {
+    let mut codes = HashSet::new();
+    events.retain(|event| codes.insert(event.code.clone()));
+}
+
+/// Sort events chronologically
+///
+/// Events starting at the same time are sorted by title.
+/// The sort is stable: events with the same start time and title keep their order.
+///
+/// # Example
+///
+/// ```
+/// use epitok::event::{sort_events, Event};
+///
+/// # fn example(events: &mut [Event]) {
+/// sort_events(events);
+/// # }
+/// ```
+pub fn sort_events(events: &mut [Event]) {
+    events.sort_by(|a, b| a.start.cmp(&b.start).then_with(|| a.title.cmp(&b.title)));
+}
+
+/// Build an event from an entry of the planning
+fn parse_planning_event(event: &serde_json::Value) -> Result<Event, Error> {
+    let code = match construct_code(event) {
+        Some(code) => code,
+        None => return Err(Error::EventURL),
+    };
+
+    let title = match event["acti_title"].as_str() {
+        Some(title) => intra::decode_entities(title),
+        None => return Err(Error::Title),
+    };
+
+    let module = match event["titlemodule"].as_str() {
+        Some(module) => intra::decode_entities(module),
+        None => return Err(Error::Module),
+    };
+
+    let start = match parse_time(event, Time::Start) {
+        Some(start) => start,
+        None => return Err(Error::TimeStart),
+    };
+
+    let end = match parse_time(event, Time::End) {
+        Some(end) => end,
+        None => return Err(Error::TimeEnd),
+    };
+
+    let room = parse_room(event);
+
+    let kind = ActivityKind::from(event["type_code"].as_str().unwrap_or_default());
+    let kind_label = event["type_title"].as_str().unwrap_or_default().to_string();
+
+    let registered_count = intra::parse_value(&event["total_students_registered"]);
+
+    let semester = intra::parse_value(&event["semester"]);
+
+    let appointment = !matches!(event["is_rdv"].as_str(), Some("0") | None);
+
+    let students = Vec::new();
+
+    Ok(Event {
+        code,
+        title,
+        module,
+        start,
+        end,
+        room,
+        kind,
+        kind_label,
+        registered_count,
+        semester,
+        description: None,
+        project: None,
+        appointment,
+        students,
+        slots: Vec::new(),
+    })
+}
+
+/// Read a date given by a user, relative to a reference date
+///
+/// Supported formats:
+/// - `today`, `yesterday`, `tomorrow`
+/// - a number of days after or before the reference: `+2`, `-1`
+/// - a date in `YYYY-MM-DD` format
+///
+/// # Arguments
+///
+/// * `input` - Date given by the user
+/// * `reference` - Date of "today"
+///
+/// # Example
+///
+/// ```
+/// use chrono::NaiveDate;
+/// use epitok::event::parse_date_arg;
+///
+/// let today = NaiveDate::from_ymd_opt(2020, 7, 1).unwrap();
+/// assert_eq!(parse_date_arg("+3", today).unwrap().to_string(), "2020-07-04");
+/// ```
+pub fn parse_date_arg(
+    input: &str,
+    reference: chrono::NaiveDate,
+) -> Result<chrono::NaiveDate, Error> {
+    let invalid = || Error::DateArg(input.to_string());
+
+    let days: i64 = match input.trim() {
+        "today" => 0,
+        "yesterday" => -1,
+        "tomorrow" => 1,
+        offset if offset.starts_with('+') || offset.starts_with('-') => {
+            offset.parse().map_err(|_| invalid())?
+        }
+        date => return chrono::NaiveDate::parse_from_str(date, "%Y-%m-%d").map_err(|_| invalid()),
+    };
+
+    chrono::Duration::try_days(days)
+        .and_then(|days| reference.checked_add_signed(days))
+        .ok_or_else(invalid)
+}
+
+/// Get events of a date given by a user, such as `tomorrow` or `+2`
+///
+/// The date is relative to the current day of the computer, see `parse_date_arg` for formats.
+///
+/// # Arguments
+///
+/// * `list` - Where events will be stored
 /// * `autologin` - User autologin link
-/// * `raw_date` - Date in `YYYY-MM-DD` format
+/// * `input` - Date given by the user
+pub async fn list_events_relative(
+    list: &mut Vec<Event>,
+    autologin: &str,
+    input: &str,
+) -> Result<usize, Box<dyn error::Error>> {
+    let date = parse_date_arg(input, chrono::Local::now().date_naive())?;
+
+    list_events_on(list, autologin, date).await
+}
+
+/// Timezone of the intra, used to know which day it is on the planning
+pub const INTRA_TIMEZONE: chrono_tz::Tz = chrono_tz::Europe::Paris;
+
+/// Get date of an instant in a timezone
 ///
-/// # Return value
-/// On success the number of retrieved events will be returned.
+/// # Arguments
 ///
-/// On failure the error type will be returned
+/// * `tz` - Timezone to get the date in
+/// * `instant` - Moment to get the date of
 ///
 /// # Example
 ///
-/// Get a vector of events from a particular date and print their name
+/// ```
+/// use chrono::{TimeZone, Utc};
+/// use epitok::event::{date_in, INTRA_TIMEZONE};
+///
+/// // 23:30 UTC is the next day in Paris, both in winter (UTC+1) and in summer (UTC+2)
+/// let before_dst = Utc.with_ymd_and_hms(2020, 3, 28, 23, 30, 0).unwrap();
+/// assert_eq!(date_in(INTRA_TIMEZONE, before_dst).to_string(), "2020-03-29");
+///
+/// // 22:30 UTC is still the same day in winter, but the next day in summer
+/// let after_dst = Utc.with_ymd_and_hms(2020, 3, 29, 22, 30, 0).unwrap();
+/// assert_eq!(date_in(INTRA_TIMEZONE, after_dst).to_string(), "2020-03-30");
+/// let winter = Utc.with_ymd_and_hms(2020, 10, 25, 22, 30, 0).unwrap();
+/// assert_eq!(date_in(INTRA_TIMEZONE, winter).to_string(), "2020-10-25");
+/// ```
+pub fn date_in(tz: chrono_tz::Tz, instant: chrono::DateTime<chrono::Utc>) -> chrono::NaiveDate {
+    instant.with_timezone(&tz).date_naive()
+}
+
+/// Get today's events
+///
+/// "Today" is computed with the timezone of the computer,
+/// use `list_events_on_intra_today` to get the current day of the intra.
+pub async fn list_events_today(
+    list: &mut Vec<Event>,
+    autologin: &str,
+) -> Result<usize, Box<dyn error::Error>> {
+    list_events_on(list, autologin, chrono::Local::now().date_naive()).await
+}
+
+/// Get today's events, "today" being computed in a timezone
+///
+/// # Arguments
+///
+/// * `list` - Where events will be stored
+/// * `autologin` - User autologin link
+/// * `tz` - Timezone used to know which day it is
+pub async fn list_events_today_tz(
+    list: &mut Vec<Event>,
+    autologin: &str,
+    tz: chrono_tz::Tz,
+) -> Result<usize, Box<dyn error::Error>> {
+    list_events_on(list, autologin, date_in(tz, chrono::Utc::now())).await
+}
+
+/// Get today's events, "today" being the current day in Paris time like on the intra
+///
+/// Unlike `list_events_today`, this does not depend on the timezone of the computer.
+pub async fn list_events_on_intra_today(
+    list: &mut Vec<Event>,
+    autologin: &str,
+) -> Result<usize, Box<dyn error::Error>> {
+    list_events_today_tz(list, autologin, INTRA_TIMEZONE).await
+}
+
+/// Get scholar year of a date, as used in codes of events
+///
+/// On the intra, a scholar year is named after the year it starts in,
+/// and a new one starts on the 1st of August.
+///
+/// # Example
+///
+/// ```
+/// use chrono::NaiveDate;
+/// use epitok::event::current_scolar_year;
+///
+/// let september = NaiveDate::from_ymd_opt(2020, 9, 14).unwrap();
+/// assert_eq!(current_scolar_year(september), "2020");
+/// ```
+pub fn current_scolar_year(today: chrono::NaiveDate) -> String {
+    let year = if today.month() >= 8 {
+        today.year()
+    } else {
+        today.year() - 1
+    };
+
+    year.to_string()
+}
+
+/// Get first and last day (Monday and Sunday) of the week of a date
+///
+/// # Example
+///
+/// ```
+/// use chrono::NaiveDate;
+/// use epitok::event::week_range;
+///
+/// let monday = NaiveDate::from_ymd_opt(2020, 6, 29).unwrap();
+/// let wednesday = NaiveDate::from_ymd_opt(2020, 7, 1).unwrap();
+/// let sunday = NaiveDate::from_ymd_opt(2020, 7, 5).unwrap();
+///
+/// assert_eq!(week_range(monday), (monday, sunday));
+/// assert_eq!(week_range(wednesday), (monday, sunday));
+/// assert_eq!(week_range(sunday), (monday, sunday));
+/// ```
+pub fn week_range(date: chrono::NaiveDate) -> (chrono::NaiveDate, chrono::NaiveDate) {
+    let monday = date - chrono::Duration::days(date.weekday().num_days_from_monday().into());
+    let sunday = monday + chrono::Duration::days(6);
+
+    (monday, sunday)
+}
+
+/// Get first and last day of the month of a date
+///
+/// # Example
+///
+/// ```
+/// use chrono::NaiveDate;
+/// use epitok::event::month_range;
+///
+/// let date = NaiveDate::from_ymd_opt(2020, 2, 14).unwrap();
+/// let (first, last) = month_range(date);
+///
+/// assert_eq!(first, NaiveDate::from_ymd_opt(2020, 2, 1).unwrap());
+/// assert_eq!(last, NaiveDate::from_ymd_opt(2020, 2, 29).unwrap());
+/// ```
+pub fn month_range(date: chrono::NaiveDate) -> (chrono::NaiveDate, chrono::NaiveDate) {
+    let first = date - chrono::Duration::days((date.day() - 1).into());
+    let next_month = first + chrono::Duration::days(31);
+    let last = next_month - chrono::Duration::days(next_month.day().into());
+
+    (first, last)
+}
+
+/// Get events of the week of a date
+///
+/// On the intra, weeks start on Monday and finish on Sunday.
+///
+/// # Arguments
+///
+/// * `list` - Where events will be stored
+/// * `autologin` - User autologin link
+/// * `raw_date` - Any date of the week in `YYYY-MM-DD` format
+pub async fn list_events_week(
+    list: &mut Vec<Event>,
+    autologin: &str,
+    raw_date: &str,
+) -> Result<usize, Box<dyn error::Error>> {
+    let date = chrono::NaiveDate::parse_from_str(raw_date, "%Y-%m-%d")?;
+    let (start, end) = week_range(date);
+
+    list_events_range_on(list, autologin, start, end).await
+}
+
+/// Get events of the current week, from Monday to Sunday
+pub async fn list_events_current_week(
+    list: &mut Vec<Event>,
+    autologin: &str,
+) -> Result<usize, Box<dyn error::Error>> {
+    let (start, end) = week_range(chrono::Local::now().date_naive());
+
+    list_events_range_on(list, autologin, start, end).await
+}
+
+/// Get events of the current month
+pub async fn list_events_month(
+    list: &mut Vec<Event>,
+    autologin: &str,
+) -> Result<usize, Box<dyn error::Error>> {
+    let (start, end) = month_range(chrono::Local::now().date_naive());
+
+    list_events_range_on(list, autologin, start, end).await
+}
+
+/// Get sessions of the activity of an event
+///
+/// # Arguments
+///
+/// * `autologin` - User autologin link
+/// * `code` - Code of an event of the activity
+///
+/// # Return value
+/// On success the sessions will be returned, sorted by start time.
+/// Sessions that can't be read are skipped.
+pub async fn activity_sessions(
+    autologin: &str,
+    code: &Code,
+) -> Result<Vec<SessionRef>, Box<dyn error::Error>> {
+    let url = format!(
+        "{}/module/{}/{}/{}/{}/?format=json",
+        autologin,
+        code.year(),
+        code.module(),
+        code.instance(),
+        code.acti()
+    );
+
+    let json = intra::get_obj(&url).await?;
+
+    let mut sessions: Vec<SessionRef> = json["events"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|session| {
+            let event = session["code"].as_str()?;
+            let start = session["begin"].as_str()?;
+
+            Some(SessionRef {
+                code: Code {
+                    event: event.to_string(),
+                    ..code.clone()
+                },
+                start: chrono::NaiveDateTime::parse_from_str(start, "%Y-%m-%d %H:%M:%S").ok()?,
+            })
+        })
+        .collect();
+
+    sessions.sort_by_key(|session| session.start);
+
+    Ok(sessions)
+}
+
+/// Get an activity and its events from its code
+///
+/// # Arguments
+///
+/// * `autologin` - User autologin link
+/// * `year` - Scolar year of activity
+/// * `module` - Code of module, `X-XXX-000`
+/// * `instance` - Code of instance, `XXX-0-0`
+/// * `acti` - Code of activity, `acti-000000`
+///
+/// # Return value
+/// On success the activity will be returned, its events don't have their students:
+/// use `Event::fetch_students` to get them.
+///
+/// # Example
 ///
 /// ```no_run
-/// use epitok::event::{Event, list_events};
+/// use epitok::event::get_activity;
 ///
 /// # #[async_std::main]
 /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
-/// let date_str = "2020-07-01";
 /// let autologin = "https://intra.epitech.eu/auth-abcdefghijklmnopqrstuvwxyz1234567890abcd";
-/// let mut events: Vec<Event> = Vec::new();
 ///
-/// list_events(&mut events, &autologin, date_str).await?;
-/// for event in events {
-///     println!("event: {} - {}", event.title(), event.module());
+/// let mut activity = get_activity(autologin, "2020", "B-CPE-110", "PAR-1-1", "acti-123456").await?;
+/// println!("{}: {} sessions", activity.title(), activity.events.len());
+/// for event in activity.events.iter_mut() {
+///     event.fetch_students(autologin).await?;
 /// }
 /// # Ok(())
 /// # }
 /// ```
-pub async fn list_events(
-    list: &mut Vec<Event>,
+pub async fn get_activity(
     autologin: &str,
-    raw_date: &str,
-) -> Result<usize, Box<dyn error::Error>> {
-    // check if the date provided is valid
-    if let Err(e) = chrono::NaiveDate::parse_from_str(&raw_date, "%Y-%m-%d") {
-        return Err(e.into());
-    }
+    year: &str,
+    module: &str,
+    instance: &str,
+    acti: &str,
+) -> Result<Activity, Box<dyn error::Error>> {
+    let url = format!(
+        "{}/module/{}/{}/{}/{}/?format=json",
+        autologin, year, module, instance, acti
+    );
 
-    // clear vector if it's not empty
-    if !list.is_empty() {
-        list.clear();
-    }
+    let json = intra::get_obj(&url).await?;
+
+    Ok(parse_activity(&json)?)
+}
+
+/// Build an activity from the JSON of its intra page
+///
+/// Events that can't be read are skipped, an activity without events has an empty list.
+///
+/// # Example
+///
+/// ```
+/// use epitok::event::parse_activity;
+///
+/// let json = serde_json::json!({
+///     "scolaryear": "2020", "codemodule": "B-CPE-110", "codeinstance": "PAR-1-1",
+///     "codeacti": "acti-123457", "title": "Bootstrap", "events": []
+/// });
+///
+/// let activity = parse_activity(&json).unwrap();
+/// assert!(activity.events.is_empty());
+/// ```
+pub fn parse_activity(json: &serde_json::Value) -> Result<Activity, Error> {
+    let code = match (
+        json["scolaryear"].as_str(),
+        json["codemodule"].as_str(),
+        json["codeinstance"].as_str(),
+        json["codeacti"].as_str(),
+    ) {
+        (Some(year), Some(module), Some(instance), Some(acti)) => Code {
+            year: year.to_string(),
+            module: module.to_string(),
+            instance: instance.to_string(),
+            acti: acti.to_string(),
+            event: String::new(),
+        },
+        _ => return Err(Error::EventURL),
+    };
+
+    let title = match json["title"].as_str() {
+        Some(title) => intra::decode_entities(title),
+        None => return Err(Error::Title),
+    };
+
+    let module = intra::decode_entities(json["module_title"].as_str().unwrap_or_default());
+    let kind_code = json["type_code"].as_str().unwrap_or_default();
+    let kind_label = json["type_title"].as_str().unwrap_or_default().to_string();
+    let description = json["description"].as_str().unwrap_or_default().to_string();
+
+    let project = parse_project(json, &code).map(|project| project.code);
+
+    let mut events = parse_activity_events(json, &code, &title, &module);
+    sort_events(&mut events);
+
+    Ok(Activity {
+        title,
+        kind: ActivityKind::from(kind_code),
+        kind_label,
+        description,
+        project,
+        events,
+    })
+}
+
+/// Build the events of an activity, from the JSON of the activity
+///
+/// Events that can't be read are skipped.
+fn parse_activity_events(
+    json: &serde_json::Value,
+    code: &Code,
+    title: &str,
+    module: &str,
+) -> Vec<Event> {
+    let kind_code = json["type_code"].as_str().unwrap_or_default();
+    let kind_label = json["type_title"].as_str().unwrap_or_default().to_string();
+    let appointment = !matches!(json["is_rdv"].as_str(), Some("0") | None);
+    let title = intra::decode_entities(title);
+    let module = intra::decode_entities(module);
+    let description = parse_description(json);
+    let project = parse_project(json, code);
+
+    json["events"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|event| {
+            let start = chrono::NaiveDateTime::parse_from_str(
+                event["begin"].as_str()?,
+                "%Y-%m-%d %H:%M:%S",
+            )
+            .ok()?;
+            let end =
+                chrono::NaiveDateTime::parse_from_str(event["end"].as_str()?, "%Y-%m-%d %H:%M:%S")
+                    .ok()?;
+
+            let room = event["location"].as_str().map(|location| Room {
+                code: location.to_string(),
+                seats: intra::parse_value(&event["seats"]),
+            });
+
+            Some(Event {
+                code: Code {
+                    event: event["code"].as_str()?.to_string(),
+                    ..code.clone()
+                },
+                title: title.clone(),
+                module: module.clone(),
+                start,
+                end,
+                room,
+                kind: ActivityKind::from(kind_code),
+                kind_label: kind_label.clone(),
+                registered_count: intra::parse_value(&event["nb_inscrits"]),
+                semester: None,
+                description: description.clone(),
+                project: project.clone(),
+                students: Vec::new(),
+                appointment,
+                slots: Vec::new(),
+            })
+        })
+        .collect()
+}
 
+/// Get all events of a module instance
+///
+/// # Arguments
+///
+/// * `autologin` - User autologin link
+/// * `year` - Scolar year of module
+/// * `module` - Code of module, `X-XXX-000`
+/// * `instance` - Code of instance, `XXX-0-0`
+///
+/// # Return value
+/// On success the events of every activity of the module will be returned, sorted chronologically.
+/// Like `list_events`, events don't have their students and activities without sessions are skipped.
+///
+/// # Example
+///
+/// ```no_run
+/// use epitok::event::list_module_events;
+///
+/// # #[async_std::main]
+/// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let autologin = "https://intra.epitech.eu/auth-abcdefghijklmnopqrstuvwxyz1234567890abcd";
+///
+/// let events = list_module_events(autologin, "2020", "B-CPE-110", "PAR-1-1").await?;
+/// for event in events {
+///     println!("{}", event);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub async fn list_module_events(
+    autologin: &str,
+    year: &str,
+    module: &str,
+    instance: &str,
+) -> Result<Vec<Event>, Box<dyn error::Error>> {
     let url = format!(
-        "{}/planning/load?format=json&start={}&end={}",
-        autologin, raw_date, raw_date
+        "{}/module/{}/{}/{}/?format=json",
+        autologin, year, module, instance
     );
 
-    let json = match intra::get_array_obj(&url).await {
-        Ok(json) => json,
-        Err(e) => {
-            return match e {
-                intra::Error::Empty => Ok(0), // No events have been retrieved
-                _ => Err(e.into()),           // Return the intra error
-            };
-        }
-    };
+    let json = intra::get_obj(&url).await?;
 
-    let mut number_events = 0;
+    Ok(parse_module_events(&json)?)
+}
 
-    for event in &json {
-        // check if this event can have tokens
-        match event["is_rdv"].as_str() {
-            Some(is_rdv) => match is_rdv {
-                "0" => (),
-                _ => continue, // Iterate over next event, skip this one
-            },
-            None => continue,
-        };
+/// Build the events of a module from the JSON of its intra page
+///
+/// Activities and events that can't be read are skipped.
+///
+/// # Example
+///
+/// ```
+/// use epitok::event::parse_module_events;
+///
+/// # fn example(json: &serde_json::Value) -> Result<(), epitok::event::Error> {
+/// for event in parse_module_events(json)? {
+///     println!("{}", event);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub fn parse_module_events(json: &serde_json::Value) -> Result<Vec<Event>, Error> {
+    let code = match (
+        json["scolaryear"].as_str(),
+        json["codemodule"].as_str(),
+        json["codeinstance"].as_str(),
+    ) {
+        (Some(year), Some(module), Some(instance)) => Code {
+            year: year.to_string(),
+            module: module.to_string(),
+            instance: instance.to_string(),
+            acti: String::new(),
+            event: String::new(),
+        },
+        _ => return Err(Error::EventURL),
+    };
 
-        let code = match construct_code(&event) {
-            Some(code) => code,
-            None => return Err(Error::EventURL.into()),
-        };
+    let module = match json["title"].as_str() {
+        Some(module) => module,
+        None => return Err(Error::Module),
+    };
 
-        let title = match event["acti_title"].as_str() {
-            Some(title) => title.to_string(),
-            None => return Err(Error::Title.into()),
+    let mut events = Vec::new();
+    for activity in json["activites"].as_array().into_iter().flatten() {
+        let (acti, title) = match (activity["codeacti"].as_str(), activity["title"].as_str()) {
+            (Some(acti), Some(title)) => (acti, title),
+            _ => continue,
         };
 
-        let module = match event["titlemodule"].as_str() {
-            Some(module) => module.to_string(),
-            None => return Err(Error::Module.into()),
+        let code = Code {
+            acti: acti.to_string(),
+            ..code.clone()
         };
 
-        let start = match parse_time(&event, Time::Start) {
-            Some(start) => start,
-            None => return Err(Error::TimeStart.into()),
-        };
+        events.extend(parse_activity_events(activity, &code, title, module));
+    }
 
-        let end = match parse_time(&event, Time::End) {
-            Some(end) => end,
-            None => return Err(Error::TimeEnd.into()),
-        };
+    dedup_events(&mut events);
+    sort_events(&mut events);
+
+    Ok(events)
+}
 
-        let students = Vec::new();
+/// Get title when getting information from a single event
+///
+/// The intranet returns the title of some events twice when getting a single event,
+/// while the planning returns it once.
+/// If the title is made of two identical halves separated by a single space,
+/// only one half is returned. Any other title is returned unchanged.
+fn dedup_title(title: &str) -> String {
+    // "X X" has an odd length, with the separating space in the middle
+    if title.len() % 2 == 1 {
+        let middle = title.len() / 2;
 
-        list.push(Event {
-            code,
-            title,
-            module,
-            start,
-            end,
-            students,
-        });
+        // a space is a single byte: both halves start on a character boundary
+        if title.as_bytes()[middle] == b' ' {
+            let (first, second) = (&title[..middle], &title[middle + 1..]);
 
-        number_events += 1;
+            if !first.is_empty() && first == second {
+                return first.to_string();
+            }
+        }
     }
 
-    Ok(number_events)
+    title.to_string()
 }
 
-/// Get today's events
-pub async fn list_events_today(
-    list: &mut Vec<Event>,
+/// Get a single event from its code
+pub async fn get_event(
     autologin: &str,
-) -> Result<usize, Box<dyn error::Error>> {
-    let date_str = chrono::Local::today().format("%Y-%m-%d").to_string();
+    year: &str,
+    module: &str,
+    instance: &str,
+    acti: &str,
+    event: &str,
+) -> Result<Event, Box<dyn error::Error>> {
+    let code = Code {
+        year: year.to_string(),
+        module: module.to_string(),
+        instance: instance.to_string(),
+        acti: acti.to_string(),
+        event: event.to_string(),
+    };
+
+    // the intra replies not found to malformed codes, find which argument is wrong
+    code.validate()?;
 
-    list_events(list, autologin, &date_str).await
+    get_event_by_code(autologin, &code).await
 }
 
-/// Get title when getting information from a single event
+/// Get a single event from its URL path, such as the one returned by `Event::code()`
+///
+/// # Arguments
 ///
-/// For some *very* odd reason, the intra is fucked up (wow shocker!)
+/// * `autologin` - User autologin link
+/// * `path` - Path of event, `/module/2019/X-XXX-000/XXX-0-0/acti-000000/event-000000`
 ///
-/// When getting the title of an event from the planning, it's all good but
-/// when getting the title of a single event, for some events the title is returned *TWICE*.
+/// # Example
 ///
-/// WHY? I DO NOT KNOW WHY
+/// ```no_run
+/// use epitok::event::get_event_by_path;
 ///
-/// So here's my dirty way to fix it:
+/// # #[async_std::main]
+/// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let autologin = "https://intra.epitech.eu/auth-abcdefghijklmnopqrstuvwxyz1234567890abcd";
+/// let path = "/module/2020/B-CPE-110/PAR-1-1/acti-123456/event-654321";
 ///
-/// 1. Split the string in half and remove the last character of the first string
-/// (there is the title twice but at least it's separated by a space)
-/// 2. Compare the two strings:
-/// * if they match, return only one part
-/// * else return the original title
-fn get_title_single_event(title: &str) -> String {
-    let mut first = String::from(title);
-    let second = first.split_off((first.len() / 2) + 1);
-    first.pop();
+/// let event = get_event_by_path(autologin, path).await?;
+/// assert_eq!(event.code(), path);
+/// # Ok(())
+/// # }
+/// ```
+pub async fn get_event_by_path(
+    autologin: &str,
+    path: &str,
+) -> Result<Event, Box<dyn error::Error>> {
+    let code: Code = path.parse()?;
 
-    if first == second {
-        first
-    } else {
-        title.to_string()
-    }
+    get_event_by_code(autologin, &code).await
+}
+
+/// Get a single event from its intra URL, as copied from a browser
+///
+/// # Arguments
+///
+/// * `autologin` - User autologin link
+/// * `url` - URL of event, such as `https://intra.epitech.eu/module/2019/X-XXX-000/XXX-0-0/acti-000000/event-000000/`
+///
+/// # Return value
+/// If the URL does not contain the path of an event, `CodeError::Format` is returned.
+/// See `Code::parse_from_url` for accepted URLs.
+pub async fn get_event_from_url(
+    autologin: &str,
+    url: &str,
+) -> Result<Event, Box<dyn error::Error>> {
+    let code = Code::parse_from_url(url).ok_or(CodeError::Format)?;
+
+    get_event_by_code(autologin, &code).await
 }
 
 /// Get a single event from its code
-pub async fn get_event(
+///
+/// # Arguments
+///
+/// * `autologin` - User autologin link
+/// * `code` - Code of event
+pub async fn get_event_by_code(
     autologin: &str,
-    year: &str,
-    module: &str,
-    instance: &str,
-    acti: &str,
-    event: &str,
+    code: &Code,
 ) -> Result<Event, Box<dyn error::Error>> {
-    let url = format!(
-        "{}/module/{}/{}/{}/{}/{}?format=json",
-        autologin, year, module, instance, acti, event
-    );
+    get_event_by_code_with_client(intra::IntraClient::shared(), autologin, code).await
+}
 
-    let json = match intra::get_obj(&url).await {
+/// Get a single event from its code, with a client instead of the shared one
+///
+/// # Arguments
+///
+/// * `client` - Client requests are made with
+/// * `autologin` - User autologin link
+/// * `code` - Code of event
+pub async fn get_event_by_code_with_client(
+    client: &intra::IntraClient,
+    autologin: &str,
+    code: &Code,
+) -> Result<Event, Box<dyn error::Error>> {
+    let url = format!("{}{}?format=json", autologin, code);
+
+    let json = match client.get_obj(&url).await {
         Ok(json) => json,
         Err(e) => return Err(e.into()),
     };
@@ -583,12 +3993,12 @@ pub async fn get_event(
     };
 
     let title = match json["acti_title"].as_str() {
-        Some(title) => get_title_single_event(title),
+        Some(title) => dedup_title(&intra::decode_entities(title)),
         None => return Err(Error::Title.into()),
     };
 
     let module = match json["module_title"].as_str() {
-        Some(module) => module.to_string(),
+        Some(module) => intra::decode_entities(module),
         None => return Err(Error::Module.into()),
     };
 
@@ -602,6 +4012,20 @@ pub async fn get_event(
         None => return Err(Error::TimeEnd.into()),
     };
 
+    let room = parse_room(&json);
+
+    let kind = ActivityKind::from(json["type_code"].as_str().unwrap_or_default());
+    let kind_label = json["type_title"].as_str().unwrap_or_default().to_string();
+
+    let registered_count = intra::parse_value(&json["total_students_registered"]);
+
+    let semester = intra::parse_value(&json["semester"]);
+
+    let appointment = !matches!(json["is_rdv"].as_str(), Some("0") | None);
+
+    let description = parse_description(&json);
+    let project = parse_project(&json, &code);
+
     let students = Vec::new();
 
     Ok(Event {
@@ -610,6 +4034,1061 @@ pub async fn get_event(
         module,
         start,
         end,
+        room,
+        kind,
+        kind_label,
+        registered_count,
+        semester,
+        description,
+        project,
+        appointment,
         students,
+        slots: Vec::new(),
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::intra::{IntraClient, IntraConfig, MockBackend};
+    use crate::student::Role;
+    use crate::testing::{
+        block_on, event, event_from, event_with, planning_entry, planning_entry_with, serve_once,
+        AUTOLOGIN, CODE,
+    };
+    use futures::future::BoxFuture;
+
+    /// Client replying to presence uploads of the event
+    fn upload_backend() -> MockBackend {
+        MockBackend::new().with_response(
+            &format!("{}{}/updateregistered?format=json", AUTOLOGIN, CODE),
+            "{}",
+        )
+    }
+
+    /// Logins of a form posted to the intra
+    fn posted_logins(form: &HashMap<String, String>) -> Vec<&str> {
+        let mut logins: Vec<&str> = form
+            .iter()
+            .filter(|(key, _)| key.ends_with("[login]"))
+            .map(|(_, login)| login.as_str())
+            .collect();
+        logins.sort_unstable();
+        logins
+    }
+
+    /// Intranet keeping the presences uploaded to it
+    #[derive(Debug, Default)]
+    struct Intra {
+        /// Presence of each registered student, in intra format
+        roster: std::sync::Mutex<BTreeMap<String, String>>,
+    }
+
+    impl intra::IntraBackend for Intra {
+        fn get<'a>(&'a self, url: &'a str) -> BoxFuture<'a, Result<String, intra::Error>> {
+            let roster = self.roster.lock().unwrap();
+            let students: Vec<serde_json::Value> = roster
+                .iter()
+                .map(|(login, present)| {
+                    serde_json::json!({ "login": login, "title": login, "present": present })
+                })
+                .collect();
+
+            let reply = match url.ends_with("/registered?format=json") {
+                true => Ok(serde_json::Value::from(students).to_string()),
+                false => Err(intra::Error::NotFound),
+            };
+            Box::pin(async move { reply })
+        }
+
+        fn post_form<'a>(
+            &'a self,
+            _: &'a str,
+            data: &'a HashMap<String, String>,
+        ) -> BoxFuture<'a, Result<String, intra::Error>> {
+            let mut roster = self.roster.lock().unwrap();
+            for (key, login) in data.iter().filter(|(key, _)| key.ends_with("[login]")) {
+                let present = key.replace("[login]", "[present]");
+                roster.insert(login.clone(), data[&present].clone());
+            }
+            Box::pin(async { Ok(String::from("{}")) })
+        }
+
+        fn post_json<'a>(
+            &'a self,
+            _: &'a str,
+            _: &'a serde_json::Value,
+        ) -> BoxFuture<'a, Result<String, intra::Error>> {
+            Box::pin(async { Err(intra::Error::NotFound) })
+        }
+    }
+
+    #[test]
+    fn verify_upload_with_cached_roster() {
+        let intra = Intra::default();
+        intra
+            .roster
+            .lock()
+            .unwrap()
+            .insert("a@epitech.eu".to_string(), "absent".to_string());
+        let client = IntraClient::with_backend(IntraConfig::default(), intra)
+            .with_cache(std::time::Duration::from_secs(60));
+
+        let mut event = event();
+        async_std::task::block_on(async {
+            // the roster is cached before the upload
+            event
+                .fetch_students_with_client(&client, AUTOLOGIN)
+                .await
+                .unwrap();
+            event.set_student_present("a@epitech.eu").unwrap();
+
+            let report = event.upload_and_verify(&client, AUTOLOGIN).await.unwrap();
+            assert!(report.mismatched.is_empty());
+        });
+        assert!(!event.students_dirty());
+    }
+
+    #[test]
+    fn upload_modified_students_only() {
+        let backend = upload_backend();
+        let client = IntraClient::with_backend(IntraConfig::default(), backend.clone());
+        let mut event = event_with(&[("a@epitech.eu", "present"), ("b@epitech.eu", "present")]);
+        event.set_student_missing("b@epitech.eu").unwrap();
+
+        let options = SaveOptions::new();
+        async_std::task::block_on(event.upload_students(&client, AUTOLOGIN, &options)).unwrap();
+
+        let posted = backend.posted_forms();
+        assert_eq!(posted.len(), 1);
+        assert_eq!(posted_logins(&posted[0].1), ["b@epitech.eu"]);
+        assert!(!event.students_dirty());
+    }
+
+    #[test]
+    fn upload_nothing_when_unchanged() {
+        let backend = upload_backend();
+        let client = IntraClient::with_backend(IntraConfig::default(), backend.clone());
+        let mut event = event_with(&[("a@epitech.eu", "present")]);
+
+        let options = SaveOptions::new();
+        async_std::task::block_on(event.upload_students(&client, AUTOLOGIN, &options)).unwrap();
+
+        assert!(backend.posted_forms().is_empty());
+    }
+
+    #[test]
+    fn upload_skips_unmarked_students() {
+        let backend = upload_backend();
+        let client = IntraClient::with_backend(IntraConfig::default(), backend.clone());
+        let mut event = event_with(&[("a@epitech.eu", "present"), ("b@epitech.eu", "")]);
+        event.set_student_missing("a@epitech.eu").unwrap();
+
+        let options = SaveOptions::new().all_students();
+        async_std::task::block_on(event.upload_students(&client, AUTOLOGIN, &options)).unwrap();
+
+        let posted = backend.posted_forms();
+        assert_eq!(posted_logins(&posted[0].1), ["a@epitech.eu"]);
+    }
+
+    #[test]
+    fn upload_all_students_with_unmarked() {
+        let backend = upload_backend();
+        let client = IntraClient::with_backend(IntraConfig::default(), backend.clone());
+        let mut event = event_with(&[
+            ("a@epitech.eu", "present"),
+            ("b@epitech.eu", ""),
+            ("c@epitech.eu", "absent"),
+        ]);
+
+        let options = SaveOptions::new().all_students().include_unmarked();
+        async_std::task::block_on(event.upload_students(&client, AUTOLOGIN, &options)).unwrap();
+
+        let posted = backend.posted_forms();
+        assert_eq!(
+            posted_logins(&posted[0].1),
+            ["a@epitech.eu", "b@epitech.eu", "c@epitech.eu"]
+        );
+    }
+
+    #[cfg(feature = "csv")]
+    #[test]
+    fn csv_roundtrip() {
+        let mut event = event_with(&[("a@epitech.eu", "present"), ("b@epitech.eu", "")]);
+        event.set_student_late("a@epitech.eu").unwrap();
+        event.set_student_excused("b@epitech.eu").unwrap();
+        let csv = event.export_to_csv().unwrap();
+
+        let mut restored = event_with(&[("a@epitech.eu", "present"), ("b@epitech.eu", "")]);
+        let import = restored.import_presence_from_csv(&csv).unwrap();
+
+        assert_eq!(import.updated, 2);
+        assert!(import.unknown.is_empty() && import.invalid.is_empty());
+        assert_eq!(
+            restored.get_student("a@epitech.eu").unwrap().get_presence(),
+            &Presence::Late
+        );
+        assert_eq!(
+            restored.get_student("b@epitech.eu").unwrap().get_presence(),
+            &Presence::Excused
+        );
+    }
+
+    #[cfg(feature = "csv")]
+    #[test]
+    fn csv_export_header() {
+        let event = event_with(&[("a@epitech.eu", "absent")]);
+        let csv = event.export_to_csv().unwrap();
+
+        assert_eq!(
+            csv,
+            "login,name,presence\na@epitech.eu,a@epitech.eu,absent\n"
+        );
+    }
+
+    #[cfg(feature = "csv")]
+    #[test]
+    fn csv_import_with_and_without_header() {
+        let mut event = event_with(&[("a@epitech.eu", "")]);
+
+        let import = event
+            .import_presence_from_csv("login,presence\na@epitech.eu,present\n")
+            .unwrap();
+        assert_eq!(import.updated, 1);
+
+        let import = event
+            .import_presence_from_csv("a@epitech.eu,absent\n")
+            .unwrap();
+        assert_eq!(import.updated, 1);
+        assert!(event
+            .get_student("a@epitech.eu")
+            .unwrap()
+            .get_presence()
+            .is_absent());
+    }
+
+    #[cfg(feature = "csv")]
+    #[test]
+    fn csv_import_unknown_login() {
+        let mut event = event_with(&[("a@epitech.eu", "")]);
+
+        let import = event
+            .import_presence_from_csv("z@epitech.eu,present\n")
+            .unwrap();
+
+        assert_eq!(import.updated, 0);
+        assert_eq!(import.unknown, ["z@epitech.eu"]);
+    }
+
+    #[cfg(feature = "csv")]
+    #[test]
+    fn csv_import_invalid_presence() {
+        let mut event = event_with(&[("a@epitech.eu", "absent")]);
+
+        let import = event
+            .import_presence_from_csv("a@epitech.eu,presnt\n")
+            .unwrap();
+
+        assert_eq!(import.updated, 0);
+        assert_eq!(
+            import.invalid,
+            [("a@epitech.eu".to_string(), "presnt".to_string())]
+        );
+        assert!(event
+            .get_student("a@epitech.eu")
+            .unwrap()
+            .get_presence()
+            .is_absent());
+    }
+
+    #[test]
+    fn markable_events_count_failed_checks() {
+        let mut other = planning_entry();
+        other["codeevent"] = "event-654322".into();
+        let planning = serde_json::Value::from(vec![planning_entry(), other]);
+
+        // rights of the second event can't be checked
+        let backend = MockBackend::new()
+            .with_response(
+                &format!(
+                    "{}/planning/load?format=json&start=2020-07-01&end=2020-07-01",
+                    AUTOLOGIN
+                ),
+                &planning.to_string(),
+            )
+            .with_response(
+                &format!("{}{}/registered?format=json", AUTOLOGIN, CODE),
+                "[]",
+            );
+        let client = IntraClient::with_backend(IntraConfig::default(), backend);
+
+        let mut list = Vec::new();
+        let date = chrono::NaiveDate::from_ymd_opt(2020, 7, 1).unwrap();
+        let listing =
+            async_std::task::block_on(markable_events(&client, &mut list, AUTOLOGIN, date))
+                .unwrap();
+
+        assert_eq!(listing.markable, 1);
+        assert_eq!(listing.filtered, 0);
+        assert_eq!(listing.failed, 1);
+        assert_eq!(list[0].code(), CODE);
+    }
+
+    #[test]
+    fn dedup_doubled_titles() {
+        assert_eq!(dedup_title("Bootstrap Bootstrap"), "Bootstrap");
+        assert_eq!(
+            dedup_title("Kick-off Pushswap Kick-off Pushswap"),
+            "Kick-off Pushswap"
+        );
+        assert_eq!(dedup_title("Day 01 Day 01"), "Day 01");
+        assert_eq!(dedup_title("Soutenance Soutenance"), "Soutenance");
+    }
+
+    #[test]
+    fn dedup_accented_titles() {
+        assert_eq!(dedup_title("Rentrée Rentrée"), "Rentrée");
+        assert_eq!(
+            dedup_title("Présentation du module Présentation du module"),
+            "Présentation du module"
+        );
+        assert_eq!(dedup_title("Réunion"), "Réunion");
+        assert_eq!(dedup_title("éé"), "éé");
+    }
+
+    #[test]
+    fn keep_titles_not_doubled() {
+        assert_eq!(dedup_title("Bootstrap"), "Bootstrap");
+        assert_eq!(
+            dedup_title("Bootstrap Bootstrap 2"),
+            "Bootstrap Bootstrap 2"
+        );
+        assert_eq!(
+            dedup_title("Review Review session"),
+            "Review Review session"
+        );
+        assert_eq!(dedup_title("abcabc"), "abcabc");
+        assert_eq!(dedup_title("a  a"), "a  a");
+        assert_eq!(dedup_title(" "), " ");
+        assert_eq!(dedup_title(""), "");
+    }
+
+    #[test]
+    fn semester_from_number_or_string() {
+        let number = event_from(planning_entry_with(serde_json::json!({ "semester": 1 })));
+        let string = event_from(planning_entry_with(serde_json::json!({ "semester": "2" })));
+        let missing = event();
+
+        assert_eq!(number.semester(), Some(1));
+        assert_eq!(string.semester(), Some(2));
+        assert_eq!(missing.semester(), None);
+        assert_eq!(missing.promo_hint(), Some(1));
+    }
+
+    #[test]
+    fn session_index_in_activity() {
+        let event = event();
+        let session = |event: &str, day: u32| SessionRef {
+            code: format!("/module/2020/B-CPE-110/PAR-1-1/acti-123456/{}", event)
+                .parse()
+                .unwrap(),
+            start: chrono::NaiveDate::from_ymd_opt(2020, 7, day)
+                .unwrap()
+                .and_hms_opt(9, 0, 0)
+                .unwrap(),
+        };
+        let sessions = [
+            session("event-654320", 1),
+            session("event-654321", 8),
+            session("event-654322", 15),
+        ];
+
+        assert_eq!(event.session_index(&sessions), Some((2, 3)));
+        assert_eq!(event.session_index(&sessions[..1]), None);
+    }
+
+    /// Event of the planning between two times
+    fn event_between(start: &str, end: &str) -> Event {
+        event_from(planning_entry_with(
+            serde_json::json!({ "start": start, "end": end }),
+        ))
+    }
+
+    /// Time of a test, `YYYY-MM-DD HH:MM`
+    fn at(time: &str) -> chrono::NaiveDateTime {
+        chrono::NaiveDateTime::parse_from_str(time, "%Y-%m-%d %H:%M").unwrap()
+    }
+
+    #[test]
+    fn duration_of_events() {
+        let event = event_between("2020-07-01 09:00:00", "2020-07-01 11:00:00");
+        assert_eq!(event.duration(), chrono::Duration::hours(2));
+
+        // end before start
+        let event = event_between("2020-07-01 11:00:00", "2020-07-01 09:00:00");
+        assert_eq!(event.duration(), chrono::Duration::zero());
+    }
+
+    #[test]
+    fn skip_times_that_cant_be_read() {
+        let entry =
+            planning_entry_with(serde_json::json!({ "start": "2020-07-01 9h", "end": "tomorrow" }));
+        let listing = parse_planning(&[entry], &ListOptions::new());
+
+        assert!(listing.events.is_empty());
+        assert_eq!(listing.skipped.len(), 1);
+    }
+
+    #[test]
+    fn ongoing_between_start_and_end() {
+        let event = event_between("2020-07-01 09:00:00", "2020-07-01 11:00:00");
+
+        assert!(event.is_future_at(at("2020-07-01 08:59")));
+        assert!(event.is_ongoing_at(at("2020-07-01 09:00")));
+        assert!(event.is_ongoing_at(at("2020-07-01 10:59")));
+        assert!(event.is_past_at(at("2020-07-01 11:00")));
+        assert!(!event.is_ongoing_at(at("2020-07-01 11:00")));
+    }
+
+    #[test]
+    fn ongoing_over_midnight() {
+        let night = event_between("2020-07-01 22:00:00", "2020-07-02 02:00:00");
+
+        assert!(night.is_future_at(at("2020-07-01 21:00")));
+        assert!(night.is_ongoing_at(at("2020-07-01 23:30")));
+        assert!(night.is_ongoing_at(at("2020-07-02 01:00")));
+        assert!(!night.is_past_at(at("2020-07-02 01:59")));
+        assert!(night.is_past_at(at("2020-07-02 02:00")));
+        assert!(!night.is_future_at(at("2020-07-02 01:00")));
+    }
+
+    /// Names of students, in order
+    fn names(event: &Event) -> Vec<&str> {
+        event.students.iter().map(Student::get_name).collect()
+    }
+
+    #[test]
+    fn sort_students_by_presence_then_name() {
+        let mut event = event();
+        for (name, presence) in [
+            ("Eve", Presence::Missing),
+            ("Bob", Presence::Present),
+            ("Dan", Presence::None),
+            ("Alice", Presence::Missing),
+            ("Carol", Presence::Late),
+        ] {
+            let login = format!("{}@epitech.eu", name.to_lowercase());
+            let student = Student::builder()
+                .login(&login)
+                .name(name)
+                .presence(presence);
+            event.students.push(student.build());
+        }
+
+        event.sort_students_by_name();
+        assert_eq!(names(&event), ["Alice", "Bob", "Carol", "Dan", "Eve"]);
+
+        event.sort_students_by_presence();
+        assert_eq!(names(&event), ["Bob", "Carol", "Dan", "Alice", "Eve"]);
+    }
+
+    #[test]
+    fn attendance_without_students() {
+        let event = event();
+
+        assert_eq!(event.count_students(), 0);
+        assert_eq!(event.count_present(), 0);
+        assert_eq!(event.attendance_rate(), 0.0);
+    }
+
+    #[test]
+    fn attendance_counts_late_as_present() {
+        let event = event_with(&[
+            ("a@epitech.eu", "present"),
+            ("b@epitech.eu", "absent"),
+            ("c@epitech.eu", "N/A"),
+            ("d@epitech.eu", "failed"),
+            ("e@epitech.eu", ""),
+        ]);
+        let mut event = event;
+        event.set_student_late("e@epitech.eu").unwrap();
+
+        assert_eq!(event.count_students(), 5);
+        assert_eq!(event.count_present(), 2);
+        assert_eq!(event.count_missing(), 1);
+        assert_eq!(event.count_not_applicable(), 1);
+        assert_eq!(event.count_failed(), 1);
+        assert_eq!(event.attendance_rate(), 0.4);
+    }
+
+    #[test]
+    fn attendance_without_staff() {
+        let mut event = event_with(&[("a@epitech.eu", "present"), ("b@epitech.eu", "absent")]);
+        event.students.push(
+            Student::builder()
+                .login("assi.stant@epitech.eu")
+                .presence(Presence::Missing)
+                .role(Role::Assistant)
+                .build(),
+        );
+
+        assert_eq!(event.count_students(), 2);
+        assert_eq!(event.count_missing(), 1);
+        assert_eq!(event.attendance_rate(), 0.5);
+    }
+
+    #[test]
+    fn set_presence_of_unknown_student() {
+        let mut event = event_with(&[("a@epitech.eu", "")]);
+
+        assert!(event
+            .set_student_presence("a@epitech.eu", Presence::Present)
+            .is_ok());
+
+        let error = event
+            .set_student_presence("not.registered@epitech.eu", Presence::Present)
+            .unwrap_err();
+        assert!(
+            matches!(&error, Error::StudentNotFound(login) if login == "not.registered@epitech.eu")
+        );
+        assert!(error.to_string().contains("not.registered@epitech.eu"));
+    }
+
+    #[test]
+    fn students_missing_from_module() {
+        let mut event = event();
+        event
+            .students
+            .push(Student::builder().login("First.Last@epitech.eu").build());
+        event
+            .students
+            .push(Student::builder().login("anony.mous@epitech.eu").build());
+        let roster = [
+            Student::builder().login("first.last@epitech.eu").build(),
+            Student::builder().login("a.b@epitech.eu").build(),
+        ];
+
+        let missing = event.missing_from_module(&roster);
+        assert_eq!(missing.len(), 1);
+        assert_eq!(missing[0].get_login(), "anony.mous@epitech.eu");
+
+        let unregistered = event.unregistered_from_module(&roster);
+        assert_eq!(unregistered.len(), 1);
+        assert_eq!(unregistered[0].get_login(), "a.b@epitech.eu");
+    }
+
+    #[test]
+    fn dirty_when_presence_modified() {
+        let mut event = event_with(&[("a@epitech.eu", "")]);
+        assert!(!event.students_dirty());
+
+        // presences modified directly on students are tracked too
+        let student = event.get_student_mut("a@epitech.eu").unwrap();
+        student.set_presence(Presence::Present);
+        assert!(event.students_dirty());
+
+        // back to the presence it was fetched with
+        let student = event.get_student_mut("a@epitech.eu").unwrap();
+        student.set_presence(Presence::None);
+        assert!(!event.students_dirty());
+    }
+
+    #[test]
+    fn dry_run_without_staff() {
+        let mut event = event();
+        for (login, presence) in [
+            ("first.last@epitech.eu", Presence::Present),
+            ("second.one@epitech.eu", Presence::Missing),
+            ("third.one@epitech.eu", Presence::NotApplicable),
+        ] {
+            event
+                .students
+                .push(Student::builder().login(login).presence(presence).build());
+        }
+        event.students.push(
+            Student::builder()
+                .login("assi.stant@epitech.eu")
+                .presence(Presence::Present)
+                .role(Role::Assistant)
+                .build(),
+        );
+
+        // 3 students produce 6 keys, the assistant is not uploaded
+        let changes = event.save_changes_dry_run();
+        assert_eq!(changes.len(), 6);
+        assert_eq!(changes["items[0][login]"], "first.last@epitech.eu");
+        assert_eq!(changes["items[0][present]"], "present");
+        assert_eq!(changes["items[1][login]"], "second.one@epitech.eu");
+        assert_eq!(changes["items[1][present]"], "absent");
+        assert_eq!(changes["items[2][login]"], "third.one@epitech.eu");
+        assert_eq!(changes["items[2][present]"], "N/A");
+        assert_eq!(event.staff().len(), 1);
+    }
+
+    #[test]
+    fn dry_run_with_modified_students_only() {
+        let mut event = event_with(&[
+            ("first.last@epitech.eu", "present"),
+            ("late.comer@epitech.eu", "absent"),
+            ("other.one@epitech.eu", "absent"),
+        ]);
+        assert!(event.save_changes_dry_run().is_empty());
+
+        event.set_student_present("late.comer@epitech.eu").unwrap();
+
+        let changes = event.save_changes_dry_run();
+        assert_eq!(changes.len(), 2);
+        assert_eq!(changes["items[0][login]"], "late.comer@epitech.eu");
+        assert_eq!(changes["items[0][present]"], "present");
+    }
+
+    #[test]
+    fn dry_run_with_unmarked_students() {
+        let mut event = event();
+        let marked = Student::builder()
+            .login("first.last@epitech.eu")
+            .presence(Presence::Present);
+        event.students.push(marked.build());
+        event
+            .students
+            .push(Student::builder().login("not.marked@epitech.eu").build());
+
+        let changes = event.save_changes_dry_run_with_options(&SaveOptions::new());
+        assert_eq!(changes.len(), 2);
+        assert!(!changes
+            .values()
+            .any(|login| login == "not.marked@epitech.eu"));
+
+        let options = SaveOptions::new().all_students().include_unmarked();
+        let changes = event.save_changes_dry_run_with_options(&options);
+        assert_eq!(changes.len(), 4);
+        assert_eq!(changes["items[1][login]"], "not.marked@epitech.eu");
+        assert_eq!(changes["items[1][present]"], "");
+    }
+
+    #[test]
+    fn code_from_url() {
+        let path = "/module/2019/B-CPE-100/PAR-1-1/acti-123456/event-654321";
+        let code = Code::parse_from_url(path).unwrap();
+        assert_eq!(code.module(), "B-CPE-100");
+        assert_eq!(code.event(), "event-654321");
+        assert_eq!(code.to_string(), path);
+
+        let full = format!("https://intra.epitech.eu{}/", path);
+        assert_eq!(Code::parse_from_url(&full).unwrap().to_string(), path);
+        let registered = format!("https://intra.epitech.eu{}/registered?format=json", path);
+        assert_eq!(Code::parse_from_url(&registered).unwrap().to_string(), path);
+
+        assert!(Code::parse_from_url("/module/2019/B-CPE-100/PAR-1-1").is_none());
+        assert!(Code::parse_from_url("https://intra.epitech.eu/planning/").is_none());
+    }
+
+    #[test]
+    fn code_from_path() {
+        let code: Code = CODE.parse().unwrap();
+        assert_eq!(code.instance(), "PAR-1-1");
+        assert_eq!(code.to_string(), CODE);
+        assert_eq!(code.to_string().parse::<Code>().unwrap(), code);
+
+        let error = "/module/2020/B-CPE-110/PAR-1-1/acti-123456".parse::<Code>();
+        assert!(matches!(error, Err(CodeError::Format)));
+        let error = "/module/2020/B-CPE-110/PAR-1-1/123456/event-654321".parse::<Code>();
+        assert!(matches!(
+            error,
+            Err(CodeError::Invalid { field: "acti", .. })
+        ));
+        let error = "/module/2020/B-CPE-110/PAR-1-1/acti-12345/event-654321".parse::<Code>();
+        assert!(matches!(
+            error,
+            Err(CodeError::Invalid { field: "acti", .. })
+        ));
+    }
+
+    #[test]
+    fn filter_matches_code() {
+        let event = event();
+        assert!(EventFilter::new().instance("par-1-1").matches(&event));
+        assert!(EventFilter::new().year("2020").matches(&event));
+        assert!(!EventFilter::new().instance("LYN-1-1").matches(&event));
+        assert!(!EventFilter::new().year("2021").matches(&event));
+    }
+
+    #[test]
+    fn list_options_filter() {
+        let entry = |instance: &str, title: &str| {
+            planning_entry_with(serde_json::json!({
+                "codeinstance": instance,
+                "codeevent": format!("event-{}", instance),
+                "acti_title": title,
+            }))
+        };
+        let json = [
+            entry("PAR-1-1", "Bootstrap"),
+            entry("par-2-1", "Bootstrap"),
+            entry("PAR-2-1", "Review"),
+        ];
+
+        let filter = EventFilter::new().instance("PAR-2-1").title("boot");
+        let listing = parse_planning(&json, &ListOptions::new().filter(filter));
+        assert_eq!(listing.events.len(), 1);
+        assert_eq!(listing.events[0].code.instance(), "par-2-1");
+    }
+
+    #[test]
+    fn list_events_with_mock() {
+        let planning = serde_json::json!([planning_entry()]);
+        let backend = MockBackend::new().with_response(
+            &format!(
+                "{}/planning/load?format=json&start=2020-07-01&end=2020-07-01",
+                AUTOLOGIN
+            ),
+            &planning.to_string(),
+        );
+        let client = IntraClient::with_backend(IntraConfig::default(), backend);
+        let date = chrono::NaiveDate::from_ymd_opt(2020, 7, 1).unwrap();
+
+        let mut events = Vec::new();
+        let count = futures::executor::block_on(list_events_range_on_with_client(
+            &client,
+            &mut events,
+            AUTOLOGIN,
+            date,
+            date,
+        ))
+        .unwrap();
+        assert_eq!(count, 1);
+        assert_eq!(events[0].title(), "Bootstrap");
+    }
+
+    #[test]
+    fn list_events_once_per_range() {
+        let entry = |event: &str, start: &str, end: &str| {
+            planning_entry_with(serde_json::json!({
+                "codeevent": event, "acti_title": "Hackathon", "start": start, "end": end,
+            }))
+        };
+        let planning = serde_json::json!([
+            entry("event-000003", "2020-07-05 10:00:00", "2020-07-05 18:00:00"),
+            entry("event-000001", "2020-07-03 10:00:00", "2020-07-03 18:00:00"),
+            entry("event-000002", "2020-07-04 10:00:00", "2020-07-04 18:00:00"),
+            entry("event-000003", "2020-07-05 10:00:00", "2020-07-05 18:00:00"),
+        ]);
+        let backend = MockBackend::new().with_response(
+            &format!(
+                "{}/planning/load?format=json&start=2020-07-03&end=2020-07-05",
+                AUTOLOGIN
+            ),
+            &planning.to_string(),
+        );
+        let client = IntraClient::with_backend(IntraConfig::default(), backend);
+        let day = |day| chrono::NaiveDate::from_ymd_opt(2020, 7, day).unwrap();
+
+        let mut events = Vec::new();
+        let count = futures::executor::block_on(list_events_range_on_with_client(
+            &client,
+            &mut events,
+            AUTOLOGIN,
+            day(3),
+            day(5),
+        ))
+        .unwrap();
+        assert_eq!(count, 3);
+        let dates: Vec<_> = events.iter().map(|event| event.date()).collect();
+        assert_eq!(dates, vec![day(3), day(4), day(5)]);
+    }
+
+    #[test]
+    fn list_untouched_on_server_error() {
+        let (url, server) = serve_once("500 Internal Server Error", "");
+        let autologin = format!("{}/auth-abcdefghijklmnopqrstuvwxyz1234567890abcd", url);
+        // fail on the first reply, without retrying
+        let client = IntraClient::with_config(IntraConfig {
+            max_retries: 0,
+            ..IntraConfig::default()
+        });
+        let date = chrono::NaiveDate::from_ymd_opt(2020, 7, 4).unwrap();
+
+        let mut events = vec![event()];
+        let error = block_on(list_events_range_on_with_client(
+            &client,
+            &mut events,
+            &autologin,
+            date,
+            date,
+        ))
+        .unwrap_err();
+        server.join().unwrap();
+
+        assert!(matches!(
+            error.downcast_ref::<intra::Error>(),
+            Some(intra::Error::ServerError(500))
+        ));
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].title(), "Bootstrap");
+    }
+
+    #[test]
+    fn group_events_by_day() {
+        let entry = |event: &str, start: &str, end: &str| {
+            planning_entry_with(
+                serde_json::json!({ "codeevent": event, "start": start, "end": end }),
+            )
+        };
+        let events = vec![
+            entry("event-3", "2020-07-03 14:00:00", "2020-07-03 16:00:00"),
+            entry("event-2", "2020-07-03 09:00:00", "2020-07-03 11:00:00"),
+            entry("event-1", "2020-07-01 09:00:00", "2020-07-01 11:00:00"),
+        ]
+        .into_iter()
+        .map(event_from)
+        .collect();
+        let day = |day| chrono::NaiveDate::from_ymd_opt(2020, 7, day).unwrap();
+
+        let days = group_by_date(events);
+        assert_eq!(days.len(), 2);
+        assert_eq!(days[&day(1)].len(), 1);
+        assert!(!days.contains_key(&day(2)));
+        assert_eq!(days[&day(3)][0].code.event(), "event-2");
+        assert_eq!(days[&day(3)][1].code.event(), "event-3");
+    }
+
+    #[test]
+    fn planning_skips_entries_without_title() {
+        let mut untitled = planning_entry_with(serde_json::json!({
+            "codeacti": "acti-123457", "codeevent": "event-654322",
+            "start": "2020-07-01 14:00:00", "end": "2020-07-01 16:00:00",
+        }));
+        untitled.as_object_mut().unwrap().remove("acti_title");
+
+        let listing = parse_planning(&[planning_entry(), untitled], &ListOptions::new());
+        assert_eq!(listing.events.len(), 1);
+        assert_eq!(listing.events[0].title(), "Bootstrap");
+        assert_eq!(listing.skipped.len(), 1);
+        assert!(matches!(listing.skipped[0], (1, Error::Title)));
+    }
+
+    #[test]
+    fn dedup_keeps_first_occurrence() {
+        let entry = |title: &str, start: &str| {
+            event_from(planning_entry_with(serde_json::json!({
+                "acti_title": title, "start": start, "end": "2020-07-02 18:00:00",
+            })))
+        };
+        let mut events = vec![
+            entry("Bootstrap", "2020-07-01 09:00:00"),
+            entry("Bootstrap (day 2)", "2020-07-02 09:00:00"),
+        ];
+        assert_eq!(events[0], events[1]);
+
+        dedup_events(&mut events);
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].title(), "Bootstrap");
+    }
+
+    #[test]
+    fn sort_by_start_then_title() {
+        let mut events: Vec<Event> = [
+            ("event-000001", "Review", "2020-07-02 14:00:00"),
+            ("event-000002", "Kick-off", "2020-07-01 09:00:00"),
+            ("event-000003", "Bootstrap", "2020-07-02 09:00:00"),
+            ("event-000004", "Apero", "2020-07-02 09:00:00"),
+        ]
+        .iter()
+        .map(|(event, title, start)| {
+            event_from(planning_entry_with(serde_json::json!({
+                "codeevent": event, "acti_title": title, "start": start, "end": "2020-07-02 18:00:00",
+            })))
+        })
+        .collect();
+
+        sort_events(&mut events);
+        let titles: Vec<&str> = events.iter().map(|event| event.title()).collect();
+        assert_eq!(titles, vec!["Kick-off", "Apero", "Bootstrap", "Review"]);
+    }
+
+    #[test]
+    fn activity_with_events() {
+        let json = serde_json::json!({
+            "scolaryear": "2020", "codemodule": "B-CPE-110", "codeinstance": "PAR-1-1",
+            "codeacti": "acti-123456", "module_title": "Unix & C Lab Seminar",
+            "title": "Pushswap", "type_code": "proj", "type_title": "Project",
+            "description": "Sort numbers", "is_projet": true,
+            "events": [
+                {
+                    "code": "event-654322", "begin": "2020-07-02 09:00:00",
+                    "end": "2020-07-02 11:00:00", "location": "FR/PAR/Voltaire/Amphi-A"
+                },
+                {
+                    "code": "event-654321", "begin": "2020-07-01 09:00:00",
+                    "end": "2020-07-01 11:00:00"
+                }
+            ]
+        });
+
+        let activity = parse_activity(&json).unwrap();
+        assert_eq!(activity.title(), "Pushswap");
+        assert_eq!(
+            activity.project(),
+            Some("/module/2020/B-CPE-110/PAR-1-1/acti-123456/project")
+        );
+        assert_eq!(activity.events.len(), 2);
+        assert_eq!(activity.events[0].code(), CODE);
+        assert_eq!(activity.events[1].room().unwrap().name(), "Amphi A");
+        assert!(activity.events[1].students.is_empty());
+        assert_eq!(activity.events[0].description(), Some("Sort numbers"));
+        assert_eq!(activity.events[0].project().unwrap().title, "Pushswap");
+    }
+
+    #[test]
+    fn activity_without_project() {
+        let json = serde_json::json!({
+            "scolaryear": "2020", "codemodule": "B-CPE-110", "codeinstance": "PAR-1-1",
+            "codeacti": "acti-123457", "title": "Bootstrap", "events": []
+        });
+
+        let activity = parse_activity(&json).unwrap();
+        assert_eq!(activity.project(), None);
+        assert!(activity.events.is_empty());
+    }
+
+    #[test]
+    fn module_events_sorted() {
+        let json = serde_json::json!({
+            "scolaryear": "2020", "codemodule": "B-CPE-110", "codeinstance": "PAR-1-1",
+            "title": "Unix & C Lab Seminar",
+            "activites": [
+                {
+                    "codeacti": "acti-123456", "title": "Bootstrap", "type_code": "class",
+                    "events": [{
+                        "code": "event-654322", "begin": "2020-07-08 09:00:00", "end": "2020-07-08 11:00:00"
+                    }]
+                },
+                { "codeacti": "acti-123457", "title": "Pushswap", "type_code": "proj", "events": [] },
+                {
+                    "codeacti": "acti-123458", "title": "Kick-off", "type_code": "class",
+                    "events": [{
+                        "code": "event-654321", "begin": "2020-07-01 09:00:00", "end": "2020-07-01 11:00:00"
+                    }]
+                }
+            ]
+        });
+
+        let events = parse_module_events(&json).unwrap();
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0].title(), "Kick-off");
+        assert_eq!(events[0].module(), "Unix & C Lab Seminar");
+        assert_eq!(
+            events[1].code(),
+            "/module/2020/B-CPE-110/PAR-1-1/acti-123456/event-654322"
+        );
+    }
+
+    #[test]
+    fn urls_of_event() {
+        let event = event();
+        let base = "https://intra.epitech.eu/module/2020/B-CPE-110/PAR-1-1/acti-123456/";
+        assert_eq!(event.intra_url_on(intra::INTRA_URL), base);
+        assert_eq!(
+            event.event_url_on(intra::INTRA_URL),
+            format!("{}event-654321/", base)
+        );
+        assert_eq!(
+            event.registered_url_on(intra::INTRA_URL),
+            format!("{}event-654321/registered", base)
+        );
+    }
+
+    #[test]
+    fn verify_report_compare() {
+        let remote = event_with(&[
+            ("first.last@epitech.eu", "present"),
+            ("late.comer@epitech.eu", "absent"),
+            ("not.changed@epitech.eu", "absent"),
+        ]);
+        let expected = vec![
+            ("First.Last@epitech.eu".to_string(), Presence::Present),
+            ("late.comer@epitech.eu".to_string(), Presence::Present),
+            ("gone.away@epitech.eu".to_string(), Presence::Missing),
+        ];
+
+        let report = VerifyReport::compare(&expected, &remote.students);
+        assert_eq!(report.confirmed, 1);
+        assert_eq!(
+            report.mismatched,
+            vec![
+                (
+                    "late.comer@epitech.eu".to_string(),
+                    Presence::Present,
+                    Presence::Missing
+                ),
+                (
+                    "gone.away@epitech.eu".to_string(),
+                    Presence::Missing,
+                    Presence::None
+                ),
+            ]
+        );
+        assert!(!report.is_confirmed());
+    }
+
+    #[test]
+    fn date_args() {
+        let today = chrono::NaiveDate::from_ymd_opt(2020, 7, 1).unwrap();
+        let parse = |input| parse_date_arg(input, today).unwrap().to_string();
+
+        assert_eq!(parse("today"), "2020-07-01");
+        assert_eq!(parse("tomorrow"), "2020-07-02");
+        assert_eq!(parse("yesterday"), "2020-06-30");
+        assert_eq!(parse("+3"), "2020-07-04");
+        assert_eq!(parse("-1"), "2020-06-30");
+        assert_eq!(parse("2020-09-01"), "2020-09-01");
+
+        let error = parse_date_arg("someday", today).unwrap_err();
+        assert_eq!(error.to_string(), "Invalid date: someday");
+    }
+
+    #[test]
+    fn scolar_year_starts_in_august() {
+        let year = |y, m, d| current_scolar_year(chrono::NaiveDate::from_ymd_opt(y, m, d).unwrap());
+
+        assert_eq!(year(2020, 7, 31), "2019");
+        assert_eq!(year(2020, 8, 1), "2020");
+        assert_eq!(year(2020, 9, 14), "2020");
+        assert_eq!(year(2021, 1, 4), "2020");
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_keeps_saved_presences() {
+        let event = event_with(&[
+            ("first.last@epitech.eu", "present"),
+            ("anony.mous@epitech.eu", ""),
+        ]);
+
+        let saved = serde_json::to_value(&event).unwrap();
+        let restored: Event = serde_json::from_value(saved.clone()).unwrap();
+        assert_eq!(serde_json::to_value(&restored).unwrap(), saved);
+        // presences saved on the intra are restored: nothing is uploaded again
+        assert!(!restored.students[0].is_modified());
+        assert!(restored.save_changes_dry_run().is_empty());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_reads_older_versions() {
+        // fields added in later versions are optional
+        let old: Event = serde_json::from_str(
+            r#"{
+                "code": { "year": "2020", "module": "B-CPE-110", "instance": "PAR-1-1", "acti": "acti-123456", "event": "event-654321" },
+                "title": "Bootstrap",
+                "module": "Unix & C Lab Seminar",
+                "start": "2020-07-01T09:00:00",
+                "end": "2020-07-01T11:00:00",
+                "students": [{ "login": "first.last@epitech.eu", "name": "First Last", "presence": "Present" }]
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(old.code(), CODE);
+        assert_eq!(old.students[0].get_login(), "first.last@epitech.eu");
+    }
+}
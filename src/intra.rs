@@ -2,10 +2,16 @@
 //!
 //! Communication to the Epitech intranet, to send and receive data
 
+use futures::future::BoxFuture;
+use futures::stream::{self, StreamExt};
 use std::collections::HashMap;
+use std::future::Future;
+use std::str::FromStr;
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::{Duration, Instant};
 use std::{error, fmt};
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone)]
 /// Error possibilities
 pub enum Error {
     /// No network access
@@ -20,92 +26,1324 @@ pub enum Error {
     Parsing,
     /// Empty JSON reply
     Empty,
+    /// Intranet took too long to reply
+    Timeout,
+    /// Too many requests have been made to the intranet
+    RateLimit,
+    /// Intranet replied with an error, with its message
+    UpdateFailed(String),
+    /// Intranet replied with an HTML page instead of JSON (maintenance), with the start of the page
+    MaintenancePage(String),
+    /// Intranet replied with an unexpected HTTP status code
+    ServerError(u16),
 }
 
 impl error::Error for Error {}
 
 impl fmt::Display for Error {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        let message = match *self {
-            Error::Network => "No internet access",
-            Error::AccessDenied => "You do not have permission to access this resource",
-            Error::NotFound => "Could not find page on the Epitech intranet",
-            Error::IntraDown => "Could not connect to the Epitech intranet",
-            Error::Parsing => "Failed to parse retrieved data from the intranet",
-            Error::Empty => "Empty JSON array",
+        let message = match self {
+            Error::Network => "No internet access".into(),
+            Error::AccessDenied => "You do not have permission to access this resource".into(),
+            Error::NotFound => "Could not find page on the Epitech intranet".into(),
+            Error::IntraDown => "Could not connect to the Epitech intranet".into(),
+            Error::Parsing => "Failed to parse retrieved data from the intranet".into(),
+            Error::Empty => "Empty JSON array".into(),
+            Error::Timeout => "The Epitech intranet took too long to reply".into(),
+            Error::RateLimit => "Too many requests to the Epitech intranet, try again later".into(),
+            Error::UpdateFailed(message) => {
+                format!("The Epitech intranet refused the update: {}", message)
+            }
+            Error::ServerError(status) => format!("Intranet returned HTTP {}", status),
+            Error::MaintenancePage(_) => {
+                "The Epitech intranet replied with a web page, it is probably in maintenance".into()
+            }
         };
         write!(f, "{}", message)
     }
 }
 
-/// Make a request to get content from a URL
-async fn get_content(url: &str) -> Result<String, Error> {
-    // make network request to intra
-    let intra_req = match reqwest::get(url).await {
-        Ok(body) => body,
-        Err(e) => {
-            eprintln!("[epitok]: Network error: {}", e);
-            return Err(Error::Network);
+/// URL of the Epitech intranet
+pub const INTRA_URL: &str = "https://intra.epitech.eu";
+
+/// Hide autologin tokens from a text
+///
+/// Every autologin token found is shortened to its first 4 characters,
+/// so URLs and error messages can be printed without leaking the credentials.
+///
+/// # Example
+///
+/// ```
+/// use epitok::intra::redact_autologin;
+///
+/// let autologin = "https://intra.epitech.eu/auth-abcdefghijklmnopqrstuvwxyz1234567890abcd";
+/// let redacted = redact_autologin(autologin);
+///
+/// assert_eq!(redacted, "https://intra.epitech.eu/auth-abcd…[redacted]");
+/// assert!(!redacted.contains("abcdefghijklmnopqrstuvwxyz1234567890abcd"));
+/// ```
+pub fn redact_autologin(text: &str) -> String {
+    static TOKEN: OnceLock<regex::Regex> = OnceLock::new();
+
+    let re = TOKEN.get_or_init(|| {
+        regex::Regex::new("auth-([a-z0-9]{4})[a-z0-9]+").expect("invalid autologin rule")
+    });
+
+    re.replace_all(text, "auth-${1}…[redacted]").into_owned()
+}
+
+/// Decode HTML entities sent by the intranet in titles and names
+///
+/// Named entities commonly used by the intranet and numeric entities are decoded,
+/// unknown entities are left as is.
+/// Entities encoded twice are decoded too, so decoding a decoded text does not change it.
+///
+/// # Example
+///
+/// ```
+/// use epitok::intra::decode_entities;
+///
+/// assert_eq!(decode_entities("C &amp; Unix"), "C & Unix");
+/// ```
+pub fn decode_entities(text: &str) -> String {
+    let mut decoded = decode_entities_once(text);
+
+    // the intranet sometimes encodes entities twice
+    while decoded.contains('&') {
+        let again = decode_entities_once(&decoded);
+        if again == decoded {
+            break;
+        }
+        decoded = again;
+    }
+
+    decoded
+}
+
+/// Decode HTML entities of a text, a single time
+fn decode_entities_once(text: &str) -> String {
+    let mut decoded = String::with_capacity(text.len());
+    let mut rest = text;
+
+    while let Some(start) = rest.find('&') {
+        decoded.push_str(&rest[..start]);
+        rest = &rest[start..];
+
+        let entity = rest
+            .find(';')
+            .filter(|&end| end <= 10)
+            .and_then(|end| Some((decode_entity(&rest[1..end])?, end)));
+
+        match entity {
+            Some((character, end)) => {
+                decoded.push(character);
+                rest = &rest[end + 1..];
+            }
+            None => {
+                decoded.push('&');
+                rest = &rest[1..];
+            }
         }
+    }
+
+    decoded.push_str(rest);
+    decoded
+}
+
+/// Get character of an HTML entity, without its `&` and `;`
+fn decode_entity(entity: &str) -> Option<char> {
+    if let Some(number) = entity.strip_prefix('#') {
+        let code = match number.strip_prefix(['x', 'X']) {
+            Some(hex) => u32::from_str_radix(hex, 16).ok()?,
+            None => number.parse().ok()?,
+        };
+        return char::from_u32(code);
+    }
+
+    let character = match entity {
+        "amp" => '&',
+        "lt" => '<',
+        "gt" => '>',
+        "quot" => '"',
+        "apos" => '\'',
+        "nbsp" => '\u{a0}',
+        "lsquo" => '‘',
+        "rsquo" => '’',
+        "ldquo" => '“',
+        "rdquo" => '”',
+        "laquo" => '«',
+        "raquo" => '»',
+        "hellip" => '…',
+        "ndash" => '–',
+        "mdash" => '—',
+        "euro" => '€',
+        "deg" => '°',
+        "aacute" => 'á',
+        "agrave" => 'à',
+        "acirc" => 'â',
+        "auml" => 'ä',
+        "ccedil" => 'ç',
+        "eacute" => 'é',
+        "egrave" => 'è',
+        "ecirc" => 'ê',
+        "euml" => 'ë',
+        "iacute" => 'í',
+        "icirc" => 'î',
+        "iuml" => 'ï',
+        "oacute" => 'ó',
+        "ocirc" => 'ô',
+        "ouml" => 'ö',
+        "uacute" => 'ú',
+        "ugrave" => 'ù',
+        "ucirc" => 'û',
+        "uuml" => 'ü',
+        "Agrave" => 'À',
+        "Eacute" => 'É',
+        "Egrave" => 'È',
+        "Ecirc" => 'Ê',
+        "Ccedil" => 'Ç',
+        _ => return None,
     };
 
+    Some(character)
+}
+
+/// Read a value the intra sends either as a string or as a number, such as `42` or `"42"`
+pub(crate) fn parse_value<T: FromStr>(json: &serde_json::Value) -> Option<T> {
+    match json {
+        serde_json::Value::String(value) => value.parse().ok(),
+        serde_json::Value::Number(value) => value.to_string().parse().ok(),
+        _ => None,
+    }
+}
+
+#[derive(Debug, Clone)]
+/// # Intranet configuration
+///
+/// Settings applied to every request made to the intranet
+///
+/// # Example
+///
+/// ```
+/// use epitok::intra::{IntraClient, IntraConfig};
+/// use std::time::Duration;
+///
+/// let config = IntraConfig {
+///     timeout: Duration::from_secs(10),
+///     ..IntraConfig::default()
+/// };
+/// let client = IntraClient::with_config(config);
+/// assert_eq!(client.config().timeout, Duration::from_secs(10));
+/// ```
+pub struct IntraConfig {
+    /// Maximum duration of a request, 30 seconds by default
+    pub timeout: Duration,
+    /// Maximum number of times a request is retried after a network failure, see `retry_with_backoff`
+    pub max_retries: u32,
+    /// User agent sent with every request
+    pub user_agent: String,
+    /// URL of a proxy used for every request, such as `http://proxy.example.com:8080`
+    ///
+    /// The intranet is only available with HTTPS: the proxy must support HTTPS tunneling (`CONNECT`).
+    pub proxy: Option<String>,
+    /// URL of the intranet replacing the domain of autologin links, such as `http://localhost:8080`
+    ///
+    /// This allows to use a staging intranet, or a local server in tests.
+    pub base_url: Option<String>,
+    /// Duration during which replies are reused instead of making new requests, disabled when zero (default)
+    pub cache_ttl: Duration,
+    /// Number of consecutive failures of the intranet (`Error::IntraDown` or `Error::ServerError` with a 5xx status code)
+    /// opening the circuit breaker, disabled when zero (default)
+    ///
+    /// While the circuit is open, requests fail with `Error::IntraDown` without being made.
+    pub circuit_breaker_threshold: u32,
+    /// Duration after which an open circuit lets a single request through, to check if the intranet is back
+    pub circuit_breaker_reset_after: Duration,
+    /// Maximum number of requests made at the same time by `parallel_get`
+    pub max_concurrent_requests: usize,
+}
+
+impl Default for IntraConfig {
+    fn default() -> Self {
+        IntraConfig {
+            timeout: Duration::from_secs(30),
+            max_retries: 3,
+            user_agent: format!("epitok/{}", env!("CARGO_PKG_VERSION")),
+            proxy: None,
+            base_url: None,
+            cache_ttl: Duration::ZERO,
+            circuit_breaker_threshold: 0,
+            circuit_breaker_reset_after: Duration::from_secs(30),
+            max_concurrent_requests: 4,
+        }
+    }
+}
+
+impl IntraConfig {
+    /// Create the default configuration, with the proxy set in the environment
+    ///
+    /// The proxy is read from `HTTPS_PROXY`, then from `HTTP_PROXY` (lowercase names are accepted too).
+    pub fn from_env() -> Self {
+        let proxy = ["HTTPS_PROXY", "https_proxy", "HTTP_PROXY", "http_proxy"]
+            .iter()
+            .filter_map(|name| std::env::var(name).ok())
+            .find(|proxy| !proxy.is_empty());
+
+        IntraConfig {
+            proxy,
+            ..IntraConfig::default()
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+/// # Intranet client
+///
+/// HTTP client used to communicate with the intranet, with its configuration
+///
+/// # Example
+///
+/// Every request sends the user agent of the configuration:
+///
+/// ```no_run
+/// use epitok::intra::{IntraClient, IntraConfig};
+/// use std::time::Duration;
+///
+/// let client = IntraClient::with_config(IntraConfig {
+///     timeout: Duration::from_secs(10),
+///     ..IntraConfig::default()
+/// });
+/// ```
+pub struct IntraClient {
+    /// Backend making the requests
+    backend: Arc<dyn IntraBackend>,
+    /// Settings of requests
+    config: IntraConfig,
+    /// Replies by URL, with when they were received
+    cache: Arc<Mutex<HashMap<String, (Instant, String)>>>,
+    /// Failures of the intranet, for the circuit breaker
+    circuit: Arc<Mutex<Circuit>>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// State of the circuit breaker of an `IntraClient`
+pub enum CircuitState {
+    /// Requests are made
+    Closed,
+    /// The intranet is down, requests fail without being made
+    Open,
+    /// The intranet was down, a single request is made to check if it is back
+    HalfOpen,
+}
+
+#[derive(Debug, Default)]
+/// Failures of the intranet
+struct Circuit {
+    /// Number of consecutive failures
+    failures: u32,
+    /// When the circuit was opened
+    opened_at: Option<Instant>,
+    /// A request is checking if the intranet is back
+    probing: bool,
+}
+
+/// Let another request check if the intranet is back when a probe is dropped before its result is recorded
+struct ProbeGuard<'a> {
+    circuit: &'a Mutex<Circuit>,
+    armed: bool,
+}
+
+impl Drop for ProbeGuard<'_> {
+    fn drop(&mut self) {
+        if !self.armed {
+            return;
+        }
+        if let Ok(mut circuit) = self.circuit.lock() {
+            circuit.probing = false;
+        }
+    }
+}
+
+impl Default for IntraClient {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl IntraClient {
+    /// Create a client with the default configuration
+    pub fn new() -> Self {
+        Self::with_config(IntraConfig::default())
+    }
+
+    /// Create a client with a configuration
+    ///
+    /// # Panics
+    ///
+    /// Like `reqwest::Client::new`, this panics if the TLS backend can't be initialized,
+    /// it also panics if the URL of the proxy is invalid.
+    /// Use `try_with_config` to handle this error.
+    pub fn with_config(config: IntraConfig) -> Self {
+        Self::try_with_config(config).expect("failed to build HTTP client")
+    }
+
+    /// Create a client with a configuration, failing if the HTTP client can't be built
+    /// or if the URL of the proxy is invalid
+    pub fn try_with_config(config: IntraConfig) -> Result<Self, reqwest::Error> {
+        let backend = ReqwestBackend::with_config(&config)?;
+
+        Ok(Self::with_backend(config, backend))
+    }
+
+    /// Create a client making its requests with a backend, such as a `MockBackend` in tests
+    ///
+    /// The timeout, user agent and proxy of the configuration are only used by `ReqwestBackend`.
+    pub fn with_backend<B: IntraBackend + 'static>(config: IntraConfig, backend: B) -> Self {
+        IntraClient {
+            backend: Arc::new(backend),
+            config,
+            cache: Arc::new(Mutex::new(HashMap::new())),
+            circuit: Arc::new(Mutex::new(Circuit::default())),
+        }
+    }
+
+    /// Get state of the circuit breaker, see `IntraConfig::circuit_breaker_threshold`
+    ///
+    /// The circuit breaker is shared with the clones of the client.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use epitok::intra::{CircuitState, IntraClient};
+    /// # fn example(client: &IntraClient) {
+    /// if client.circuit_state() == CircuitState::Open {
+    ///     eprintln!("the intranet is down, try again later");
+    /// }
+    /// # }
+    /// ```
+    pub fn circuit_state(&self) -> CircuitState {
+        let circuit = match self.circuit.lock() {
+            Ok(circuit) => circuit,
+            Err(_) => return CircuitState::Closed,
+        };
+
+        match circuit.opened_at {
+            None => CircuitState::Closed,
+            Some(opened_at) if opened_at.elapsed() >= self.config.circuit_breaker_reset_after => {
+                CircuitState::HalfOpen
+            }
+            Some(_) => CircuitState::Open,
+        }
+    }
+
+    /// Make a request, retrying after network failures, unless the circuit breaker is open
+    async fn send<T, Req, ReqFut>(&self, request: Req) -> Result<T, Error>
+    where
+        Req: FnMut() -> ReqFut,
+        ReqFut: Future<Output = Result<T, Error>>,
+    {
+        let mut probe = match self.circuit_allows() {
+            Some(probing) => ProbeGuard {
+                circuit: &self.circuit,
+                armed: probing,
+            },
+            None => return Err(Error::IntraDown),
+        };
+
+        let result =
+            retry_with_backoff(self.config.max_retries, futures_timer::Delay::new, request).await;
+
+        probe.armed = false;
+        self.circuit_record(matches!(
+            result,
+            Err(Error::IntraDown) | Err(Error::ServerError(500..=599))
+        ));
+
+        result
+    }
+
+    /// Check if the circuit breaker lets a request through, and if this request checks if the intranet is back
+    fn circuit_allows(&self) -> Option<bool> {
+        let mut circuit = match self.circuit.lock() {
+            Ok(circuit) => circuit,
+            Err(_) => return Some(false),
+        };
+
+        match circuit.opened_at {
+            None => Some(false),
+            // let a single request check if the intranet is back
+            Some(opened_at)
+                if opened_at.elapsed() >= self.config.circuit_breaker_reset_after
+                    && !circuit.probing =>
+            {
+                circuit.probing = true;
+                Some(true)
+            }
+            Some(_) => None,
+        }
+    }
+
+    /// Update the circuit breaker with the result of a request
+    fn circuit_record(&self, intra_down: bool) {
+        let mut circuit = match self.circuit.lock() {
+            Ok(circuit) => circuit,
+            Err(_) => return,
+        };
+
+        circuit.probing = false;
+
+        if !intra_down {
+            circuit.failures = 0;
+            circuit.opened_at = None;
+            return;
+        }
+
+        circuit.failures += 1;
+        let threshold = self.config.circuit_breaker_threshold;
+        if threshold > 0 && circuit.failures >= threshold {
+            circuit.opened_at = Some(Instant::now());
+        }
+    }
+
+    /// Reuse replies during a duration instead of making new requests, see `IntraConfig::cache_ttl`
+    ///
+    /// The cache is shared with the clones of the client.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use epitok::intra::IntraClient;
+    /// use std::time::Duration;
+    ///
+    /// let client = IntraClient::new().with_cache(Duration::from_secs(60));
+    /// ```
+    pub fn with_cache(mut self, ttl: Duration) -> Self {
+        self.config.cache_ttl = ttl;
+        self
+    }
+
+    /// Remove cached replies of URLs starting with a prefix
+    ///
+    /// Use it after changing data on the intranet.
+    /// Replies about an event are removed by `update_presences` when its presences are uploaded.
+    pub fn invalidate_cache(&self, url_prefix: &str) {
+        let url_prefix = self.resolve_url(url_prefix);
+
+        if let Ok(mut cache) = self.cache.lock() {
+            cache.retain(|url, _| !url.starts_with(&url_prefix));
+        }
+    }
+
+    /// Get cached reply of a URL, if it is recent enough
+    fn cached(&self, url: &str) -> Option<String> {
+        if self.config.cache_ttl.is_zero() {
+            return None;
+        }
+
+        let cache = self.cache.lock().ok()?;
+        let (received, content) = cache.get(url)?;
+
+        if received.elapsed() < self.config.cache_ttl {
+            Some(content.clone())
+        } else {
+            None
+        }
+    }
+
+    /// Get client shared by the functions of the library
+    ///
+    /// The `auth`, `event` and `student` modules make their requests with it,
+    /// so connections to the intranet are reused between requests.
+    /// It is created with the default configuration on first use, unless one was installed with `install_shared`.
+    ///
+    /// Pooled connections of the shared client are tied to the runtime that opened them.
+    /// Applications running several runtimes can create a client for each one,
+    /// and pass it to the `_with_client` functions or to `Session::with_client`.
+    pub fn shared() -> &'static IntraClient {
+        SHARED.get_or_init(IntraClient::new)
+    }
+
+    /// Get URL of the intranet of the shared client, without creating it
+    ///
+    /// `INTRA_URL` is returned until a client is installed or created,
+    /// so building a URL does not prevent installing a client later.
+    pub(crate) fn shared_base_url() -> &'static str {
+        match SHARED.get() {
+            Some(client) => client.base_url(),
+            None => INTRA_URL,
+        }
+    }
+
+    /// Install the client shared by the functions of the library, see `shared`
+    ///
+    /// This applies a configuration (proxy, base URL, cache...) or a backend to the whole library.
+    /// It must be done once, before any request is made.
+    ///
+    /// # Return value
+    /// If a shared client is already installed or in use, the client is given back.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use epitok::intra::{IntraClient, IntraConfig};
+    ///
+    /// let client = IntraClient::with_config(IntraConfig::from_env());
+    /// assert!(IntraClient::install_shared(client).is_ok());
+    /// assert!(IntraClient::install_shared(IntraClient::new()).is_err());
+    /// ```
+    pub fn install_shared(client: IntraClient) -> Result<(), Box<IntraClient>> {
+        SHARED.set(client).map_err(Box::new)
+    }
+
+    /// Get configuration of client
+    pub fn config(&self) -> &IntraConfig {
+        &self.config
+    }
+
+    /// Get URL of the intranet: the base URL of the configuration, or `INTRA_URL`
+    ///
+    /// The URL does not end with a slash.
+    /// Once the client is installed with `install_shared`,
+    /// the functions of the library make their requests to the base URL.
+    ///
+    /// # Example
+    ///
+    /// Fetch students from a local server
+    ///
+    /// ```
+    /// use epitok::intra::{IntraClient, IntraConfig};
+    ///
+    /// let client = IntraClient::with_config(IntraConfig {
+    ///     base_url: Some("http://localhost:8080/".to_string()),
+    ///     ..IntraConfig::default()
+    /// });
+    /// assert_eq!(client.base_url(), "http://localhost:8080");
+    /// ```
+    pub fn base_url(&self) -> &str {
+        match &self.config.base_url {
+            Some(base_url) => base_url.trim_end_matches('/'),
+            None => INTRA_URL,
+        }
+    }
+
+    /// Get URL where a request is made, with the domain replaced by the base URL of the configuration
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use epitok::intra::{IntraClient, IntraConfig};
+    ///
+    /// let client = IntraClient::with_config(IntraConfig {
+    ///     base_url: Some("http://localhost:8080/".to_string()),
+    ///     ..IntraConfig::default()
+    /// });
+    /// assert_eq!(
+    ///     client.resolve_url("https://intra.epitech.eu/auth-abcd/planning/load?format=json"),
+    ///     "http://localhost:8080/auth-abcd/planning/load?format=json"
+    /// );
+    /// ```
+    pub fn resolve_url(&self, url: &str) -> String {
+        let base_url = match &self.config.base_url {
+            Some(base_url) => base_url.trim_end_matches('/'),
+            None => return url.to_string(),
+        };
+
+        // skip scheme and domain of URL
+        let path = match url.find("://") {
+            Some(scheme) => {
+                let domain = &url[scheme + 3..];
+                match domain.find('/') {
+                    Some(path) => &domain[path..],
+                    None => "",
+                }
+            }
+            None => url,
+        };
+
+        format!("{}{}", base_url, path)
+    }
+
+    /// Make a request to get content from a URL, retrying after network failures
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, fields(url = %redact_autologin(url)))
+    )]
+    async fn get_content(&self, url: &str) -> Result<String, Error> {
+        let url = &self.resolve_url(url);
+
+        if let Some(content) = self.cached(url) {
+            return Ok(content);
+        }
+
+        let content = self.send(|| self.backend.get(url)).await?;
+
+        // the intra replies OK with a web page during maintenance
+        if is_html(&content) {
+            return Err(maintenance_page(&content));
+        }
+
+        if !self.config.cache_ttl.is_zero() {
+            if let Ok(mut cache) = self.cache.lock() {
+                cache.insert(url.to_string(), (Instant::now(), content.clone()));
+            }
+        }
+
+        Ok(content)
+    }
+
+    /// Get JSON object from a URL
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, fields(url = %redact_autologin(url)))
+    )]
+    pub async fn get_obj(&self, url: &str) -> Result<serde_json::Value, Error> {
+        let intra_request = self.get_content(url).await?;
+
+        // parse json object
+        match serde_json::from_str(&intra_request) {
+            Ok(json) => Ok(json),
+            Err(e) => {
+                eprintln!("[epitok] Parsing error: {}", e);
+                #[cfg(feature = "tracing")]
+                trace_error("failed to parse reply", &e);
+                Err(Error::Parsing)
+            }
+        }
+    }
+
+    /// Get JSON array from a URL
+    ///
+    /// # Return value
+    /// If the intranet replies with a web page, such as during maintenance, `Error::MaintenancePage` is returned.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use epitok::intra::{Error, IntraClient, IntraConfig, MockBackend};
+    ///
+    /// let url = "https://intra.epitech.eu/auth-abcd/planning/load?format=json";
+    /// let page = format!("<!DOCTYPE html><html><body>{}</body></html>", "Maintenance ".repeat(50));
+    /// let backend = MockBackend::new().with_response(url, &page);
+    /// let client = IntraClient::with_backend(IntraConfig::default(), backend);
+    ///
+    /// # async_std::task::block_on(async {
+    /// match client.get_array_obj(url).await {
+    ///     Err(Error::MaintenancePage(start)) => {
+    ///         assert!(start.starts_with("<!DOCTYPE html>"));
+    ///         assert_eq!(start.chars().count(), 200);
+    ///     }
+    ///     result => panic!("unexpected result: {:?}", result),
+    /// }
+    /// # });
+    /// ```
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, fields(url = %redact_autologin(url)))
+    )]
+    pub async fn get_array_obj(&self, url: &str) -> Result<Vec<serde_json::Value>, Error> {
+        let intra_request = self.get_content(url).await?;
+
+        // parse json array of objects
+        match serde_json::from_str(&intra_request) {
+            Ok(json) => Ok(json),
+            Err(_) => Err(Error::Empty), // Return Error::empty if there is nothing in the object
+        }
+    }
+
+    /// Updates presence statuses of students for an event
+    ///
+    /// # Arguments
+    ///
+    /// * `autologin` - User autologin link
+    /// * `code_event` - Url code of the event
+    /// * `students` List of students and their presence status, made with `event.export_students`
+    ///
+    /// # Return value
+    /// If the intranet replies with an error message, `Error::UpdateFailed` is returned with it.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use epitok::intra::IntraClient;
+    /// use std::collections::HashMap;
+    ///
+    /// # #[async_std::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let autologin = "https://intra.epitech.eu/auth-abcdefghijklmnopqrstuvwxyz1234567890abcd";
+    /// let code = "/module/2020/B-CPE-110/PAR-1-1/acti-123456/event-654321";
+    ///
+    /// let mut students = HashMap::new();
+    /// students.insert("items[0][login]".to_string(), "first.last@epitech.eu".to_string());
+    /// students.insert("items[0][present]".to_string(), "present".to_string());
+    /// IntraClient::new().update_presences(autologin, code, students).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, fields(event = %event_code, students = students.len()))
+    )]
+    pub async fn update_presences(
+        &self,
+        autologin: &str,
+        event_code: &str,
+        students: HashMap<String, String>,
+    ) -> Result<(), Error> {
+        let url = self.resolve_url(&format!(
+            "{}{}/updateregistered?format=json",
+            autologin, event_code
+        ));
+
+        #[cfg(feature = "tracing")]
+        tracing::debug!(url = %redact_autologin(&url), "uploading presences");
+
+        let reply = self
+            .send(|| self.backend.post_form(&url, &students))
+            .await?;
+
+        // cached replies about the event, such as its students, are outdated
+        self.invalidate_cache(&format!("{}{}/", autologin, event_code));
+
+        // the intra can reply OK with an error in the body
+        check_update_reply(&reply)
+    }
+}
+
+/// # Intranet backend
+///
+/// Makes the HTTP requests of an `IntraClient`, and turns HTTP failures into errors
+///
+/// `ReqwestBackend` makes real requests, `MockBackend` replies with fake content for tests.
+/// Requests are retried by the client, a backend makes a single attempt.
+pub trait IntraBackend: fmt::Debug + Send + Sync {
+    /// Get content of a URL
+    fn get<'a>(&'a self, url: &'a str) -> BoxFuture<'a, Result<String, Error>>;
+
+    /// Send a form to a URL, and get the content of the reply
+    fn post_form<'a>(
+        &'a self,
+        url: &'a str,
+        data: &'a HashMap<String, String>,
+    ) -> BoxFuture<'a, Result<String, Error>>;
+
+    /// Send a JSON body to a URL, and get the content of the reply
+    fn post_json<'a>(
+        &'a self,
+        url: &'a str,
+        body: &'a serde_json::Value,
+    ) -> BoxFuture<'a, Result<String, Error>>;
+}
+
+#[derive(Debug, Clone)]
+/// # Reqwest backend
+///
+/// Backend making requests to the intranet with `reqwest`, used by default
+pub struct ReqwestBackend {
+    /// HTTP client
+    client: reqwest::Client,
+}
+
+impl ReqwestBackend {
+    /// Create a backend with the timeout, user agent and proxy of a configuration
+    pub fn with_config(config: &IntraConfig) -> Result<Self, reqwest::Error> {
+        let mut builder = reqwest::Client::builder()
+            .timeout(config.timeout)
+            .user_agent(config.user_agent.as_str());
+
+        if let Some(proxy) = &config.proxy {
+            builder = builder.proxy(reqwest::Proxy::all(proxy.as_str())?);
+        }
+
+        Ok(ReqwestBackend {
+            client: builder.build()?,
+        })
+    }
+
+    /// Make a single request to get content from a URL
+    async fn get_content(&self, url: &str) -> Result<String, Error> {
+        #[cfg(feature = "tracing")]
+        tracing::debug!(url = %redact_autologin(url), "requesting intranet");
+
+        // make network request to intra
+        let intra_req = match self.client.get(url).send().await {
+            Ok(body) => body,
+            Err(e) => {
+                eprintln!(
+                    "[epitok]: Network error: {}",
+                    redact_autologin(&e.to_string())
+                );
+                #[cfg(feature = "tracing")]
+                trace_error("network error", &e);
+                return Err(request_error(&e));
+            }
+        };
+
+        #[cfg(feature = "tracing")]
+        tracing::info!(status = %intra_req.status(), "intranet replied");
+
+        // page not found
+        if intra_req.status() == reqwest::StatusCode::NOT_FOUND {
+            return Err(Error::NotFound);
+        }
+
+        let html = intra_req
+            .headers()
+            .get(reqwest::header::CONTENT_TYPE)
+            .and_then(|kind| kind.to_str().ok())
+            .is_some_and(|kind| kind.starts_with("text/html"));
+
+        let content = read_reply(intra_req).await?;
+
+        // the intra replies OK with a web page during maintenance
+        if html {
+            return Err(maintenance_page(&content));
+        }
+
+        Ok(content)
+    }
+
+    /// Make a single POST request
+    async fn post(&self, request: reqwest::RequestBuilder) -> Result<String, Error> {
+        let intra_req = match request.send().await {
+            Ok(req) => req,
+            Err(e) => {
+                eprintln!(
+                    "[epitok] Network error: {}",
+                    redact_autologin(&e.to_string())
+                );
+                #[cfg(feature = "tracing")]
+                trace_error("network error", &e);
+                return Err(request_error(&e));
+            }
+        };
+
+        #[cfg(feature = "tracing")]
+        tracing::info!(status = %intra_req.status(), "intranet replied");
+
+        read_reply(intra_req).await
+    }
+}
+
+impl IntraBackend for ReqwestBackend {
+    fn get<'a>(&'a self, url: &'a str) -> BoxFuture<'a, Result<String, Error>> {
+        Box::pin(self.get_content(url))
+    }
+
+    fn post_form<'a>(
+        &'a self,
+        url: &'a str,
+        data: &'a HashMap<String, String>,
+    ) -> BoxFuture<'a, Result<String, Error>> {
+        Box::pin(self.post(self.client.post(url).form(data)))
+    }
+
+    fn post_json<'a>(
+        &'a self,
+        url: &'a str,
+        body: &'a serde_json::Value,
+    ) -> BoxFuture<'a, Result<String, Error>> {
+        Box::pin(self.post(self.client.post(url).json(body)))
+    }
+}
+
+/// Form posted to a `MockBackend`, with its URL
+pub type PostedForm = (String, HashMap<String, String>);
+
+#[derive(Debug, Clone, Default)]
+/// # Mock backend
+///
+/// Backend replying with fake content, to test code using the intranet without network access
+///
+/// Requests to a URL without a response fail with `Error::NotFound`.
+/// Forms and JSON bodies posted to the backend are recorded, and shared with its clones.
+/// Installed with `IntraClient::install_shared`, the mock replies to the functions of the other modules.
+///
+/// # Example
+///
+/// ```
+/// use epitok::intra::{IntraClient, IntraConfig, MockBackend};
+///
+/// let url = "https://intra.epitech.eu/auth-abcd/user/?format=json";
+/// let backend = MockBackend::new().with_response(url, r#"{"login": "first.last@epitech.eu"}"#);
+/// let client = IntraClient::with_backend(IntraConfig::default(), backend);
+///
+/// let json = futures::executor::block_on(client.get_obj(url)).unwrap();
+/// assert_eq!(json["login"], "first.last@epitech.eu");
+/// ```
+pub struct MockBackend {
+    /// Content of the reply for each URL
+    pub responses: HashMap<String, String>,
+    /// Forms posted, with their URL
+    forms: Arc<Mutex<Vec<PostedForm>>>,
+    /// JSON bodies posted, with their URL
+    bodies: Arc<Mutex<Vec<(String, serde_json::Value)>>>,
+}
+
+impl MockBackend {
+    /// Create a backend without any response
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add the content replied to requests to a URL
+    pub fn with_response(mut self, url: &str, content: &str) -> Self {
+        self.responses.insert(url.to_string(), content.to_string());
+        self
+    }
+
+    /// Get forms posted to the backend and its clones, oldest first
+    pub fn posted_forms(&self) -> Vec<PostedForm> {
+        match self.forms.lock() {
+            Ok(forms) => forms.clone(),
+            Err(_) => Vec::new(),
+        }
+    }
+
+    /// Get JSON bodies posted to the backend and its clones, oldest first
+    pub fn posted_json(&self) -> Vec<(String, serde_json::Value)> {
+        match self.bodies.lock() {
+            Ok(bodies) => bodies.clone(),
+            Err(_) => Vec::new(),
+        }
+    }
+
+    /// Get content replied to a URL
+    fn reply(&self, url: &str) -> BoxFuture<'_, Result<String, Error>> {
+        let reply = self.responses.get(url).cloned().ok_or(Error::NotFound);
+        Box::pin(async move { reply })
+    }
+}
+
+impl IntraBackend for MockBackend {
+    fn get<'a>(&'a self, url: &'a str) -> BoxFuture<'a, Result<String, Error>> {
+        self.reply(url)
+    }
+
+    fn post_form<'a>(
+        &'a self,
+        url: &'a str,
+        data: &'a HashMap<String, String>,
+    ) -> BoxFuture<'a, Result<String, Error>> {
+        if let Ok(mut forms) = self.forms.lock() {
+            forms.push((url.to_string(), data.clone()));
+        }
+        self.reply(url)
+    }
+
+    fn post_json<'a>(
+        &'a self,
+        url: &'a str,
+        body: &'a serde_json::Value,
+    ) -> BoxFuture<'a, Result<String, Error>> {
+        if let Ok(mut bodies) = self.bodies.lock() {
+            bodies.push((url.to_string(), body.clone()));
+        }
+        self.reply(url)
+    }
+}
+
+/// Read the reply of the intranet, failing if its status is not OK
+async fn read_reply(intra_req: reqwest::Response) -> Result<String, Error> {
     // user does not have access (bad autologin for example)
     if intra_req.status() == reqwest::StatusCode::FORBIDDEN {
         return Err(Error::AccessDenied);
     }
 
-    // page not found
-    if intra_req.status() == reqwest::StatusCode::NOT_FOUND {
-        return Err(Error::NotFound);
+    // too many requests
+    if intra_req.status() == reqwest::StatusCode::TOO_MANY_REQUESTS {
+        return Err(Error::RateLimit);
     }
 
-    // intra is probably down
+    // intra is probably down or there is an unexpected error
     if intra_req.status() != reqwest::StatusCode::OK {
-        return Err(Error::IntraDown);
+        return Err(Error::ServerError(intra_req.status().as_u16()));
     }
 
-    // get request's content
     match intra_req.text().await {
-        Ok(raw) => Ok(raw),
+        Ok(reply) => Ok(reply),
         Err(e) => {
-            eprintln!("[epitok] Parsing error: {}", e);
+            eprintln!(
+                "[epitok] Parsing error: {}",
+                redact_autologin(&e.to_string())
+            );
+            #[cfg(feature = "tracing")]
+            trace_error("failed to read reply", &e);
             Err(Error::Parsing)
         }
     }
 }
 
-/// Get JSON object from a URL
-pub async fn get_obj(url: &str) -> Result<serde_json::Value, Error> {
-    let intra_request = match get_content(&url).await {
-        Ok(intra_request) => intra_request,
-        Err(e) => return Err(e),
-    };
+/// Send a JSON body to a URL and get the JSON reply
+///
+/// Requests are retried after network failures like the other requests of the client,
+/// and HTTP status codes are handled like in `IntraClient::update_presences`.
+///
+/// # Arguments
+///
+/// * `client` - Client making the request
+/// * `url` - URL to send the body to, including the autologin link
+/// * `body` - JSON body of the request
+///
+/// # Return value
+/// On success the JSON reply is returned, `serde_json::Value::Null` if the reply is empty.
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(skip_all, fields(url = %redact_autologin(url)))
+)]
+pub async fn post_json(
+    client: &IntraClient,
+    url: &str,
+    body: serde_json::Value,
+) -> Result<serde_json::Value, Error> {
+    let url = &client.resolve_url(url);
+
+    let reply = client.send(|| client.backend.post_json(url, &body)).await?;
 
-    // parse json object
-    match serde_json::from_str(&intra_request) {
+    if reply.trim().is_empty() {
+        return Ok(serde_json::Value::Null);
+    }
+
+    match serde_json::from_str(&reply) {
         Ok(json) => Ok(json),
         Err(e) => {
             eprintln!("[epitok] Parsing error: {}", e);
+            #[cfg(feature = "tracing")]
+            trace_error("failed to parse reply", &e);
             Err(Error::Parsing)
         }
     }
 }
 
-/// Get JSON array from a URL
-pub async fn get_array_obj(url: &str) -> Result<Vec<serde_json::Value>, Error> {
-    let intra_request = match get_content(&url).await {
-        Ok(intra_request) => intra_request,
-        Err(e) => return Err(e),
+/// Get JSON objects from several URLs concurrently
+///
+/// At most `IntraConfig::max_concurrent_requests` requests are made at the same time,
+/// so the intranet is not overwhelmed.
+///
+/// # Arguments
+///
+/// * `client` - Client making the requests
+/// * `urls` - URLs to get, including the autologin link
+///
+/// # Return value
+/// The result of each URL is returned, in the same order as the URLs.
+///
+/// # Example
+///
+/// ```
+/// # use epitok::intra::{parallel_get, IntraClient};
+/// # async fn example(client: &IntraClient) {
+/// let urls: Vec<String> = (1..=5)
+///     .map(|day| format!("https://intra.epitech.eu/auth-abcd/planning/load?day={}", day))
+///     .collect();
+/// for result in parallel_get(client, urls).await {
+///     println!("{:?}", result);
+/// }
+/// # }
+/// ```
+pub async fn parallel_get(
+    client: &IntraClient,
+    urls: Vec<String>,
+) -> Vec<Result<serde_json::Value, Error>> {
+    let limit = client.config.max_concurrent_requests.max(1);
+
+    // buffered keeps the order of the URLs
+    stream::iter(urls)
+        .map(|url| async move { client.get_obj(&url).await })
+        .buffered(limit)
+        .collect()
+        .await
+}
+
+/// Get JSON array from a URL paginated with `offset` and `limit` parameters
+///
+/// Pages are requested until a page has fewer items than `page_size`.
+///
+/// # Arguments
+///
+/// * `client` - Client making the requests
+/// * `url` - URL of the array, including the autologin link
+/// * `page_size` - Number of items requested per page, the array is requested at once if it is `0`
+///
+/// # Return value
+/// On success the items of all pages are returned, in order.
+///
+/// # Example
+///
+/// ```
+/// # use epitok::intra::{get_array_obj_paginated, Error, IntraClient};
+/// # async fn example(client: &IntraClient) -> Result<(), Error> {
+/// let url = "https://intra.epitech.eu/auth-abcd/module/board/?format=json";
+/// let items = get_array_obj_paginated(client, url, 50).await?;
+/// # Ok(())
+/// # }
+/// ```
+pub async fn get_array_obj_paginated(
+    client: &IntraClient,
+    url: &str,
+    page_size: usize,
+) -> Result<Vec<serde_json::Value>, Error> {
+    if page_size == 0 {
+        return client.get_array_obj(url).await;
+    }
+
+    let separator = if url.contains('?') { '&' } else { '?' };
+    let mut items = Vec::new();
+
+    loop {
+        let page_url = format!(
+            "{}{}offset={}&limit={}",
+            url,
+            separator,
+            items.len(),
+            page_size
+        );
+
+        let page = match client.get_array_obj(&page_url).await {
+            Ok(page) => page,
+            Err(Error::Empty) => Vec::new(), // no items after the last page
+            Err(e) => return Err(e),
+        };
+
+        let last = page.len() < page_size;
+        items.extend(page);
+
+        if last {
+            return Ok(items);
+        }
+    }
+}
+
+/// Check if a reply is an HTML page
+fn is_html(content: &str) -> bool {
+    let start: String = content.trim_start().chars().take(9).collect();
+    let start = start.to_lowercase();
+
+    start.starts_with("<!doctype") || start.starts_with("<html")
+}
+
+/// Get error of a web page replied instead of JSON, with the start of the page for diagnostics
+fn maintenance_page(content: &str) -> Error {
+    Error::MaintenancePage(content.trim_start().chars().take(200).collect())
+}
+
+/// Check the reply of the intranet to an update of presences
+///
+/// An empty reply or a JSON reply without an `error` key is a success.
+fn check_update_reply(reply: &str) -> Result<(), Error> {
+    if reply.trim().is_empty() {
+        return Ok(());
+    }
+
+    let json: serde_json::Value = match serde_json::from_str(reply) {
+        Ok(json) => json,
+        Err(_) => return Err(Error::UpdateFailed("unexpected reply".into())),
     };
 
-    // parse json array of objects
-    match serde_json::from_str(&intra_request) {
-        Ok(json) => Ok(json),
-        Err(_) => Err(Error::Empty), // Return Error::empty if there is nothing in the object
+    match &json["error"] {
+        serde_json::Value::Null => Ok(()),
+        serde_json::Value::String(message) => Err(Error::UpdateFailed(message.clone())),
+        error => Err(Error::UpdateFailed(error.to_string())),
+    }
+}
+
+/// Log an error with all its sources, without autologin tokens
+#[cfg(feature = "tracing")]
+pub(crate) fn trace_error(context: &str, e: &dyn error::Error) {
+    let mut chain = e.to_string();
+    let mut source = e.source();
+    while let Some(inner) = source {
+        chain.push_str(": ");
+        chain.push_str(&inner.to_string());
+        source = inner.source();
+    }
+
+    tracing::error!("{}: {}", context, redact_autologin(&chain));
+}
+
+/// Get error of a request that could not be made
+fn request_error(e: &reqwest::Error) -> Error {
+    if e.is_timeout() {
+        Error::Timeout
+    } else {
+        Error::Network
     }
 }
 
-/// Updates presence statuses of students for an event
+/// Client shared by the functions of the library, see `IntraClient::shared`
+static SHARED: OnceLock<IntraClient> = OnceLock::new();
+
+/// Delay before the first retry, doubled after each retry
+const FIRST_RETRY_DELAY: Duration = Duration::from_millis(100);
+
+/// Make a request, retrying it after transient failures
+///
+/// The request is retried up to `max_retries` times on `Error::Network`, `Error::Timeout`, `Error::IntraDown`
+/// and `Error::ServerError` with a 5xx status code,
+/// waiting 100 ms before the first retry, then 200 ms, 400 ms...
+/// Other errors (such as `Error::AccessDenied` or `Error::NotFound`) are returned immediately.
+///
+/// # Arguments
+///
+/// * `max_retries` - Maximum number of retries
+/// * `sleep` - Function waiting for a duration, such as `futures_timer::Delay::new`
+/// * `request` - Function making the request
+///
+/// # Example
+///
+/// ```
+/// use epitok::intra::{retry_with_backoff, Error};
+///
+/// let result = futures::executor::block_on(retry_with_backoff(
+///     3,
+///     futures_timer::Delay::new,
+///     || async { Err::<(), _>(Error::AccessDenied) },
+/// ));
+/// assert!(matches!(result, Err(Error::AccessDenied)));
+/// ```
+pub async fn retry_with_backoff<T, Req, ReqFut, Sleep, SleepFut>(
+    max_retries: u32,
+    mut sleep: Sleep,
+    mut request: Req,
+) -> Result<T, Error>
+where
+    Req: FnMut() -> ReqFut,
+    ReqFut: Future<Output = Result<T, Error>>,
+    Sleep: FnMut(Duration) -> SleepFut,
+    SleepFut: Future<Output = ()>,
+{
+    let mut delay = FIRST_RETRY_DELAY;
+    let mut retries = 0;
+
+    loop {
+        match request().await {
+            Err(Error::Network)
+            | Err(Error::Timeout)
+            | Err(Error::IntraDown)
+            | Err(Error::ServerError(500..=599))
+                if retries < max_retries =>
+            {
+                retries += 1;
+                #[cfg(feature = "tracing")]
+                tracing::debug!(retries, max_retries, "retrying request");
+                sleep(delay).await;
+                delay *= 2;
+            }
+            result => return result,
+        }
+    }
+}
+
+/// Get JSON object from a URL, with the shared client
+pub async fn get_obj(url: &str) -> Result<serde_json::Value, Error> {
+    IntraClient::shared().get_obj(url).await
+}
+
+/// Get JSON array from a URL, with the shared client
+pub async fn get_array_obj(url: &str) -> Result<Vec<serde_json::Value>, Error> {
+    IntraClient::shared().get_array_obj(url).await
+}
+
+/// Updates presence statuses of students for an event, with the shared client
 ///
 /// # Arguments
 ///
@@ -117,26 +1355,318 @@ pub async fn update_presences(
     event_code: &str,
     students: HashMap<String, String>,
 ) -> Result<(), Error> {
-    let client = reqwest::Client::new();
-    let url = format!("{}{}/updateregistered?format=json", autologin, event_code);
+    IntraClient::shared()
+        .update_presences(autologin, event_code, students)
+        .await
+}
 
-    let intra_req = match client.post(&url).form(&students).send().await {
-        Ok(req) => req,
-        Err(e) => {
-            eprintln!("[epitok] Update presences error: {}", e);
-            return Err(Error::Network);
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::student::fetch_students_with_client;
+    use crate::testing::{block_on, serve_once, AUTOLOGIN, CODE};
+    use std::cell::RefCell;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    /// Backend of an intranet under maintenance, counting requests
+    #[derive(Debug, Default)]
+    struct Maintenance(Arc<AtomicUsize>);
+
+    impl IntraBackend for Maintenance {
+        fn get<'a>(&'a self, _: &'a str) -> BoxFuture<'a, Result<String, Error>> {
+            self.0.fetch_add(1, Ordering::SeqCst);
+            Box::pin(async { Err(Error::IntraDown) })
         }
-    };
 
-    // user does not have access (bad autologin for example)
-    if intra_req.status() == reqwest::StatusCode::FORBIDDEN {
-        return Err(Error::AccessDenied);
+        fn post_form<'a>(
+            &'a self,
+            url: &'a str,
+            _: &'a HashMap<String, String>,
+        ) -> BoxFuture<'a, Result<String, Error>> {
+            self.get(url)
+        }
+
+        fn post_json<'a>(
+            &'a self,
+            url: &'a str,
+            _: &'a serde_json::Value,
+        ) -> BoxFuture<'a, Result<String, Error>> {
+            self.get(url)
+        }
     }
 
-    // intra is probably down or there is an unexpected error
-    if intra_req.status() != reqwest::StatusCode::OK {
-        return Err(Error::IntraDown);
+    /// Backend replying with the number of requests made
+    #[derive(Debug, Default)]
+    struct Counter(AtomicUsize);
+
+    impl IntraBackend for Counter {
+        fn get<'a>(&'a self, _: &'a str) -> BoxFuture<'a, Result<String, Error>> {
+            let count = self.0.fetch_add(1, Ordering::SeqCst) + 1;
+            Box::pin(async move { Ok(format!("{{\"count\": {}}}", count)) })
+        }
+
+        fn post_form<'a>(
+            &'a self,
+            _: &'a str,
+            _: &'a HashMap<String, String>,
+        ) -> BoxFuture<'a, Result<String, Error>> {
+            Box::pin(async { Ok(String::new()) })
+        }
+
+        fn post_json<'a>(
+            &'a self,
+            _: &'a str,
+            _: &'a serde_json::Value,
+        ) -> BoxFuture<'a, Result<String, Error>> {
+            Box::pin(async { Ok(String::new()) })
+        }
+    }
+
+    fn planning_url() -> String {
+        format!("{}/planning/load?format=json", AUTOLOGIN)
     }
 
-    Ok(())
+    #[test]
+    fn decode_entities_of_intra() {
+        assert_eq!(decode_entities("C &amp; Unix"), "C & Unix");
+        assert_eq!(decode_entities("O&#039;Brien"), "O'Brien");
+        assert_eq!(
+            decode_entities("&lt;b&gt; &quot;bold&quot; &apos;&#x27;"),
+            "<b> \"bold\" ''"
+        );
+        assert_eq!(
+            decode_entities("Pr&eacute;sentation &agrave; l&rsquo;&eacute;cole"),
+            "Présentation à l’école"
+        );
+        assert_eq!(decode_entities("Fran&#231;ois &#x1F600;"), "François 😀");
+        assert_eq!(decode_entities("C &amp;amp; Unix"), "C & Unix");
+    }
+
+    #[test]
+    fn decode_entities_keeps_clean_texts() {
+        assert_eq!(decode_entities("C & Unix; R&D"), "C & Unix; R&D");
+        assert_eq!(decode_entities("&unknown; &#xZZ; &"), "&unknown; &#xZZ; &");
+        assert_eq!(
+            decode_entities(&decode_entities("C &amp; Unix")),
+            "C & Unix"
+        );
+    }
+
+    #[test]
+    fn requests_send_user_agent() {
+        let (url, server) = serve_once("200 OK", "{}");
+        block_on(IntraClient::new().get_obj(&format!("{}/", url))).unwrap();
+
+        let request = server.join().unwrap().to_lowercase();
+        let user_agent = IntraConfig::default().user_agent;
+        assert!(user_agent.starts_with("epitok/"));
+        assert!(request.contains(&format!("user-agent: {}\r\n", user_agent)));
+    }
+
+    #[test]
+    fn circuit_opens_after_failures() {
+        let config = IntraConfig {
+            max_retries: 0,
+            circuit_breaker_threshold: 2,
+            circuit_breaker_reset_after: Duration::from_secs(60),
+            ..IntraConfig::default()
+        };
+        let requests = Arc::new(AtomicUsize::new(0));
+        let client = IntraClient::with_backend(config, Maintenance(requests.clone()));
+        let url = planning_url();
+
+        futures::executor::block_on(async {
+            assert!(client.get_obj(&url).await.is_err());
+            assert_eq!(client.circuit_state(), CircuitState::Closed);
+            assert!(client.get_obj(&url).await.is_err());
+            assert_eq!(client.circuit_state(), CircuitState::Open);
+
+            // fail without making a request
+            assert!(matches!(client.get_obj(&url).await, Err(Error::IntraDown)));
+        });
+        assert_eq!(requests.load(Ordering::SeqCst), 2);
+    }
+
+    #[test]
+    fn cache_until_invalidated() {
+        let client = IntraClient::with_backend(IntraConfig::default(), Counter::default())
+            .with_cache(Duration::from_secs(60));
+        let url = planning_url();
+
+        futures::executor::block_on(async {
+            assert_eq!(client.get_obj(&url).await.unwrap()["count"], 1);
+            assert_eq!(client.get_obj(&url).await.unwrap()["count"], 1);
+
+            client.invalidate_cache(&format!("{}/planning", AUTOLOGIN));
+            assert_eq!(client.get_obj(&url).await.unwrap()["count"], 2);
+        });
+    }
+
+    #[test]
+    fn requests_made_to_base_url() {
+        let body =
+            r#"[{"login": "first.last@epitech.eu", "title": "First Last", "present": "present"}]"#;
+        let (base_url, server) = serve_once("200 OK", body);
+        let client = IntraClient::with_config(IntraConfig {
+            base_url: Some(base_url.clone()),
+            ..IntraConfig::default()
+        });
+        assert_eq!(client.base_url(), base_url);
+
+        let mut students = Vec::new();
+        block_on(fetch_students_with_client(
+            &client,
+            &mut students,
+            AUTOLOGIN,
+            CODE,
+        ))
+        .unwrap();
+
+        let request = server.join().unwrap();
+        assert!(request.starts_with(&format!(
+            "GET /auth-abcdefghijklmnopqrstuvwxyz1234567890abcd{}/registered?format=json ",
+            CODE
+        )));
+        assert_eq!(students[0].get_login(), "first.last@epitech.eu");
+    }
+
+    #[test]
+    fn resolve_url_without_base_url() {
+        let url = planning_url();
+        assert_eq!(IntraClient::new().resolve_url(&url), url);
+    }
+
+    #[test]
+    fn update_failed_with_error_in_reply() {
+        let (url, server) = serve_once("200 OK", r#"{"error": "token already used"}"#);
+        let mut students = HashMap::new();
+        students.insert(
+            "items[0][login]".to_string(),
+            "first.last@epitech.eu".to_string(),
+        );
+        students.insert("items[0][present]".to_string(), "present".to_string());
+
+        let result = block_on(IntraClient::new().update_presences(&url, CODE, students));
+        server.join().unwrap();
+
+        assert!(
+            matches!(result, Err(Error::UpdateFailed(message)) if message == "token already used")
+        );
+    }
+
+    #[test]
+    fn mock_records_posted_forms() {
+        let backend = MockBackend::new().with_response(
+            &format!("{}{}/updateregistered?format=json", AUTOLOGIN, CODE),
+            "{}",
+        );
+        let client = IntraClient::with_backend(IntraConfig::default(), backend.clone());
+        let mut students = HashMap::new();
+        students.insert(
+            "items[0][login]".to_string(),
+            "first.last@epitech.eu".to_string(),
+        );
+
+        futures::executor::block_on(client.update_presences(AUTOLOGIN, CODE, students)).unwrap();
+
+        let (url, form) = &backend.posted_forms()[0];
+        assert!(url.ends_with("/updateregistered?format=json"));
+        assert_eq!(form["items[0][login]"], "first.last@epitech.eu");
+    }
+
+    #[test]
+    fn mock_without_response() {
+        let client = IntraClient::with_backend(IntraConfig::default(), MockBackend::new());
+        let missing = futures::executor::block_on(client.get_obj(&planning_url()));
+        assert!(matches!(missing, Err(Error::NotFound)));
+    }
+
+    #[test]
+    fn parallel_get_keeps_order() {
+        let urls: Vec<String> = (0..10)
+            .map(|day| format!("{}&day={}", planning_url(), day))
+            .collect();
+        let mut backend = MockBackend::new();
+        for (day, url) in urls.iter().enumerate().skip(1) {
+            backend = backend.with_response(url, &format!("{{\"day\": {}}}", day));
+        }
+        let client = IntraClient::with_backend(IntraConfig::default(), backend);
+
+        let results = futures::executor::block_on(parallel_get(&client, urls));
+        assert_eq!(results.len(), 10);
+        assert!(matches!(results[0], Err(Error::NotFound)));
+        for (day, result) in results.iter().enumerate().skip(1) {
+            assert_eq!(result.as_ref().unwrap()["day"], day);
+        }
+    }
+
+    #[test]
+    fn paginated_until_short_page() {
+        let url = format!("{}/module/board/?format=json", AUTOLOGIN);
+        let mut backend = MockBackend::new();
+        for page in 0..3 {
+            let items: Vec<usize> = (page * 5..page * 5 + 5).collect();
+            backend = backend.with_response(
+                &format!("{}&offset={}&limit=5", url, page * 5),
+                &serde_json::to_string(&items).unwrap(),
+            );
+        }
+        backend = backend.with_response(&format!("{}&offset=15&limit=5", url), "[]");
+        let client = IntraClient::with_backend(IntraConfig::default(), backend);
+
+        let items = futures::executor::block_on(get_array_obj_paginated(&client, &url, 5)).unwrap();
+        assert_eq!(items.len(), 15);
+        assert_eq!(items[0], 0);
+        assert_eq!(items[14], 14);
+    }
+
+    #[test]
+    fn retry_with_doubled_delays() {
+        let attempts = RefCell::new(0);
+        let delays = RefCell::new(Vec::new());
+
+        let result = futures::executor::block_on(retry_with_backoff(
+            3,
+            |delay| {
+                delays.borrow_mut().push(delay);
+                async {}
+            },
+            || {
+                *attempts.borrow_mut() += 1;
+                async { Err::<(), _>(Error::Network) }
+            },
+        ));
+
+        assert!(matches!(result, Err(Error::Network)));
+        assert_eq!(*attempts.borrow(), 4);
+        assert_eq!(
+            *delays.borrow(),
+            vec![
+                Duration::from_millis(100),
+                Duration::from_millis(200),
+                Duration::from_millis(400)
+            ]
+        );
+    }
+
+    #[test]
+    fn retry_server_errors_only() {
+        let attempts = RefCell::new(0);
+        let result = futures::executor::block_on(retry_with_backoff(
+            3,
+            |_| async {},
+            || {
+                *attempts.borrow_mut() += 1;
+                let status = if *attempts.borrow() < 3 { 503 } else { 400 };
+                async move { Err::<(), _>(Error::ServerError(status)) }
+            },
+        ));
+
+        assert!(matches!(result, Err(Error::ServerError(400))));
+        assert_eq!(*attempts.borrow(), 3);
+        assert_eq!(
+            Error::ServerError(503).to_string(),
+            "Intranet returned HTTP 503"
+        );
+    }
 }
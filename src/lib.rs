@@ -12,8 +12,83 @@
 //!
 //! This library will be useful only to people who are in possession of a privileged Epitech account (astek, aer, adm, pedago).
 //! So if you are just an Epitech student, this library won't be helpful for you at all.
+//!
+//! ## Features
+//!
+//! - `serde`: implements `Serialize` and `Deserialize` for events, students and their presences,
+//!   so they can be cached locally without fetching them again from the intranet
+//! - `csv`: export presences of an event to CSV, and import presences from a CSV file
+//! - `tracing`: instrument requests to the intranet with `tracing` spans and events,
+//!   autologin tokens are redacted from logged URLs
+//!
+//! ```
+//! # #[cfg(feature = "serde")]
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! use epitok::event::Event;
+//!
+//! let saved = r#"{
+//!     "code": {
+//!         "year": "2020",
+//!         "module": "B-CPE-110",
+//!         "instance": "PAR-1-1",
+//!         "acti": "acti-123456",
+//!         "event": "event-654321"
+//!     },
+//!     "title": "Bootstrap",
+//!     "module": "Unix & C Lab Seminar",
+//!     "start": "2020-07-01T09:00:00",
+//!     "end": "2020-07-01T11:00:00",
+//!     "room": { "code": "FR/PAR/Voltaire/Amphi-A", "seats": 120 },
+//!     "kind": "Tp",
+//!     "kind_label": "TP",
+//!     "registered_count": 2,
+//!     "semester": 1,
+//!     "description": "Set up your environment",
+//!     "project": null,
+//!     "students": [
+//!         {
+//!             "login": "first.last@epitech.eu",
+//!             "name": "First Last",
+//!             "presence": "Present",
+//!             "promo": "2024",
+//!             "group": null,
+//!             "semester": 1,
+//!             "role": "Student",
+//!             "saved_presence": "Present"
+//!         },
+//!         {
+//!             "login": "anony.mous@epitech.eu",
+//!             "name": "Anony Mous",
+//!             "presence": "None",
+//!             "promo": null,
+//!             "group": null,
+//!             "semester": null,
+//!             "role": "Student",
+//!             "saved_presence": "None"
+//!         }
+//!     ],
+//!     "appointment": false,
+//!     "slots": []
+//! }"#;
+//!
+//! let event: Event = serde_json::from_str(saved)?;
+//! assert_eq!(event.code(), "/module/2020/B-CPE-110/PAR-1-1/acti-123456/event-654321");
+//! assert_eq!(event.to_string(), "[09:00–11:00] Bootstrap (Unix & C Lab Seminar)");
+//! assert_eq!(event.students.len(), 2);
+//! # Ok(())
+//! # }
+//! # #[cfg(not(feature = "serde"))]
+//! # fn main() {}
+//! ```
 
 pub mod auth;
+pub mod error;
 pub mod event;
 pub mod intra;
+pub mod session;
 pub mod student;
+
+#[cfg(test)]
+mod testing;
+
+pub use error::{EpitokError, EpitokResult};
@@ -0,0 +1,208 @@
+//! # Intranet session
+//!
+//! Signed in user and intranet client, to call the intranet without passing the autologin link around.
+//!
+//! ## Example
+//!
+//! ```no_run
+//! use epitok::session::Session;
+//!
+//! # #[async_std::main]
+//! # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let autologin = "https://intra.epitech.eu/auth-abcdefghijklmnopqrstuvwxyz1234567890abcd";
+//! let session = Session::new(autologin).await?;
+//!
+//! let date = chrono::Local::now().date_naive();
+//! for mut event in session.list_events(date).await? {
+//!     let students = session.fetch_students(&mut event).await?;
+//!     println!("{}: {} students", event, students);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::auth::{self, Auth};
+use crate::error::EpitokResult;
+use crate::event::{self, Code, Event};
+use crate::intra::IntraClient;
+
+/// # Session
+///
+/// Signed in user, with the client used to reach the intranet
+#[derive(Debug)]
+pub struct Session {
+    /// Signed in user
+    auth: Auth,
+    /// Client requests are made with
+    client: IntraClient,
+}
+
+impl Session {
+    /// Sign-in with autologin link
+    ///
+    /// The autologin link is validated and the user is signed in, see `Auth::from_autologin`.
+    /// Requests are made with the shared client, see `IntraClient::shared`.
+    ///
+    /// # Arguments
+    ///
+    /// * `autologin` - User autologin link
+    pub async fn new(autologin: &str) -> EpitokResult<Self> {
+        Self::with_client(autologin, IntraClient::shared().clone()).await
+    }
+
+    /// Sign-in with autologin link, making requests with a client
+    ///
+    /// Every request of the session is made with this client, including the sign-in.
+    ///
+    /// # Arguments
+    ///
+    /// * `autologin` - User autologin link
+    /// * `client` - Client requests are made with
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use epitok::intra::{IntraClient, IntraConfig};
+    /// use epitok::session::Session;
+    ///
+    /// # #[async_std::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let autologin = "https://intra.epitech.eu/auth-abcdefghijklmnopqrstuvwxyz1234567890abcd";
+    /// let client = IntraClient::with_config(IntraConfig::from_env());
+    ///
+    /// let session = Session::with_client(autologin, client).await?;
+    /// println!("signed in as {:?}", session.auth().login());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn with_client(autologin: &str, client: IntraClient) -> EpitokResult<Self> {
+        let auth = Auth::from_autologin_with_client(&client, autologin).await?;
+
+        Ok(Self { auth, client })
+    }
+
+    /// Get signed in user
+    pub fn auth(&self) -> &Auth {
+        &self.auth
+    }
+
+    /// Get client requests are made with
+    pub fn client(&self) -> &IntraClient {
+        &self.client
+    }
+
+    /// Get events of a particular date
+    ///
+    /// See `event::list_events_range_on_with_client`.
+    ///
+    /// # Arguments
+    ///
+    /// * `date` - Date of events
+    pub async fn list_events(&self, date: chrono::NaiveDate) -> EpitokResult<Vec<Event>> {
+        let mut events = Vec::new();
+        event::list_events_range_on_with_client(
+            &self.client,
+            &mut events,
+            self.autologin()?,
+            date,
+            date,
+        )
+        .await?;
+        Ok(events)
+    }
+
+    /// Get a single event from its code
+    ///
+    /// See `event::get_event_by_code_with_client`.
+    ///
+    /// # Arguments
+    ///
+    /// * `code` - Code of event
+    pub async fn get_event(&self, code: &Code) -> EpitokResult<Event> {
+        Ok(event::get_event_by_code_with_client(&self.client, self.autologin()?, code).await?)
+    }
+
+    /// Fetch list of students of an event
+    ///
+    /// See `Event::fetch_students_with_client`.
+    ///
+    /// # Arguments
+    ///
+    /// * `event` - Event to fetch the students of
+    ///
+    /// # Return value
+    /// On success the number of students will be returned.
+    pub async fn fetch_students(&self, event: &mut Event) -> EpitokResult<usize> {
+        Ok(event
+            .fetch_students_with_client(&self.client, self.autologin()?)
+            .await?)
+    }
+
+    /// Get full URL of the intra page of an event, on the intranet of the session client
+    ///
+    /// See `Event::event_url`.
+    pub fn event_url(&self, event: &Event) -> String {
+        event.event_url_on(self.client.base_url())
+    }
+
+    /// Get full URL of the intra page of the activity of an event, on the intranet of the session client
+    ///
+    /// See `Event::intra_url`.
+    pub fn intra_url(&self, event: &Event) -> String {
+        event.intra_url_on(self.client.base_url())
+    }
+
+    /// Get full URL of the intra page of registered students of an event, on the intranet of the session client
+    ///
+    /// See `Event::registered_url`.
+    pub fn registered_url(&self, event: &Event) -> String {
+        event.registered_url_on(self.client.base_url())
+    }
+
+    /// Get autologin link of the signed in user
+    fn autologin(&self) -> Result<&str, auth::Error> {
+        self.auth
+            .autologin()
+            .as_deref()
+            .ok_or(auth::Error::NotSignedIn)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::intra::{IntraConfig, MockBackend};
+    use crate::testing::{planning_entry, AUTOLOGIN, CODE};
+
+    #[test]
+    fn requests_made_with_client() {
+        let planning = serde_json::json!([planning_entry()]);
+        let backend = MockBackend::new()
+            .with_response(
+                &format!("{}/user?format=json", AUTOLOGIN),
+                r#"{"login": "first.last@epitech.eu", "title": "First Last"}"#,
+            )
+            .with_response(
+                &format!("{}/planning/load?format=json&start=2020-07-01&end=2020-07-01", AUTOLOGIN),
+                &planning.to_string(),
+            )
+            .with_response(
+                &format!("{}{}/registered?format=json", AUTOLOGIN, CODE),
+                r#"[{"login": "student.one@epitech.eu", "title": "Student One", "present": "present"}]"#,
+            );
+        let client = IntraClient::with_backend(IntraConfig::default(), backend);
+
+        futures::executor::block_on(async {
+            let session = Session::with_client(AUTOLOGIN, client).await.unwrap();
+            assert_eq!(
+                session.auth().login().as_deref(),
+                Some("first.last@epitech.eu")
+            );
+
+            let date = chrono::NaiveDate::from_ymd_opt(2020, 7, 1).unwrap();
+            let mut events = session.list_events(date).await.unwrap();
+            assert_eq!(events.len(), 1);
+            assert_eq!(session.fetch_students(&mut events[0]).await.unwrap(), 1);
+        });
+    }
+}
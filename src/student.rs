@@ -5,15 +5,30 @@
 //! As a user of this library you should not need to use the contents of this module,
 //! they are used in internal modules of the library.
 
+use crate::event::Event;
 use crate::intra;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+use std::str::FromStr;
 use std::{error, fmt};
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 /// # Presence
 ///
 /// Presence options for students
+///
+/// # Example
+///
+/// ```
+/// use epitok::student::Presence;
+///
+/// let presence: Presence = "present".parse().unwrap();
+/// assert_eq!(presence, Presence::Present);
+/// ```
 pub enum Presence {
     /// Student does not have a status yet
+    #[default]
     None,
     /// Student was here
     Present,
@@ -21,6 +36,16 @@ pub enum Presence {
     Missing,
     /// Student can't be here
     NotApplicable,
+    /// Student arrived late
+    ///
+    /// # Note
+    /// The intra does not know this value, it is uploaded as `Present`
+    Late,
+    /// Student had a documented excuse
+    ///
+    /// # Note
+    /// The intra does not know this value, it is uploaded as `NotApplicable`
+    Excused,
     /// Student tried to enter a token but failed to save it
     ///
     /// # Note
@@ -29,8 +54,108 @@ pub enum Presence {
 }
 
 impl Presence {
+    /// Read presence from its intra value or its name
+    ///
+    /// Unknown values are considered as `Failed`.
     pub fn from(s: &str) -> Self {
-        match s {
+        s.parse().unwrap_or(Presence::Failed)
+    }
+
+    /// Value of presence sent to the intra
+    ///
+    /// The intra only knows present, absent, N/A and failed:
+    /// `Late` is uploaded as present and `Excused` as N/A.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use epitok::student::Presence;
+    ///
+    /// assert_eq!(Presence::Late.intra_value(), "present");
+    /// ```
+    pub fn intra_value(&self) -> &'static str {
+        match *self {
+            Presence::None => "", // on the intra the json value is `null`
+            Presence::Present | Presence::Late => "present",
+            Presence::Missing => "absent",
+            Presence::NotApplicable | Presence::Excused => "N/A",
+            Presence::Failed => "failed",
+        }
+    }
+
+    /// Check if a presence has been set
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use epitok::student::Presence;
+    ///
+    /// assert!(Presence::Missing.is_set());
+    /// ```
+    pub fn is_set(&self) -> bool {
+        !matches!(self, Presence::None)
+    }
+
+    /// Check if student was here
+    ///
+    /// Late students were here: they are uploaded as present.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use epitok::student::Presence;
+    ///
+    /// assert!(Presence::Late.is_present());
+    /// ```
+    pub fn is_present(&self) -> bool {
+        matches!(self, Presence::Present | Presence::Late)
+    }
+
+    /// Check if student was not here
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use epitok::student::Presence;
+    ///
+    /// assert!(Presence::Missing.is_absent());
+    /// ```
+    pub fn is_absent(&self) -> bool {
+        matches!(self, Presence::Missing)
+    }
+
+    /// Check if student can't be here
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use epitok::student::Presence;
+    ///
+    /// assert!(Presence::NotApplicable.is_not_applicable());
+    /// ```
+    pub fn is_not_applicable(&self) -> bool {
+        matches!(self, Presence::NotApplicable)
+    }
+
+    /// Check if student failed to enter a token
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use epitok::student::Presence;
+    ///
+    /// assert!(Presence::Failed.is_failed());
+    /// ```
+    pub fn is_failed(&self) -> bool {
+        matches!(self, Presence::Failed)
+    }
+}
+
+impl FromStr for Presence {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let presence = match s {
             "present" => Presence::Present,
             "Present" => Presence::Present,
             "absent" => Presence::Missing,
@@ -39,9 +164,14 @@ impl Presence {
             "NotApplicable" => Presence::NotApplicable,
             "failed" => Presence::Failed,
             "Failed" => Presence::Failed,
-            "None" => Presence::None,
-            _ => Presence::Failed,
-        }
+            "late" => Presence::Late,
+            "Late" => Presence::Late,
+            "excused" => Presence::Excused,
+            "Excused" => Presence::Excused,
+            "" | "null" | "None" => Presence::None,
+            _ => return Err(Error::Presence(s.to_string())),
+        };
+        Ok(presence)
     }
 }
 
@@ -52,13 +182,61 @@ impl fmt::Display for Presence {
             Presence::Present => "present",
             Presence::Missing => "absent",
             Presence::NotApplicable => "N/A",
+            Presence::Late => "late",
+            Presence::Excused => "excused",
             Presence::Failed => "failed",
         };
         write!(f, "{}", message)
     }
 }
 
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// Change of presence of a student
+pub struct PresenceChange {
+    /// Presence before the change
+    pub from: Presence,
+    /// Presence after the change
+    pub to: Presence,
+    /// When the change was made
+    pub timestamp: chrono::DateTime<chrono::Local>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// # Role
+///
+/// Role of someone registered to an event
+pub enum Role {
+    /// Student attending the event
+    #[default]
+    Student,
+    /// Assistant supervising the event
+    Assistant,
+    /// Teacher of the event
+    Teacher,
+}
+
+impl Role {
+    /// Get role from the registration type sent by the intra
+    ///
+    /// Unknown types are considered as students.
+    pub fn from(kind: &str) -> Self {
+        match kind {
+            "assistant" | "astek" => Role::Assistant,
+            "teacher" | "prof" => Role::Teacher,
+            _ => Role::Student,
+        }
+    }
+
+    /// Check if role is a staff member (assistant or teacher)
+    pub fn is_staff(&self) -> bool {
+        *self != Role::Student
+    }
+}
+
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 /// # Student
 ///
 /// Information about a student in an event
@@ -69,9 +247,32 @@ pub struct Student {
     name: String,
     /// Student presence status
     presence: Presence,
+    /// Promotion (year of graduation)
+    #[cfg_attr(feature = "serde", serde(default))]
+    promo: Option<String>,
+    /// Group of student
+    #[cfg_attr(feature = "serde", serde(default))]
+    group: Option<String>,
+    /// Semester of student
+    #[cfg_attr(feature = "serde", serde(default))]
+    semester: Option<i32>,
+    /// Changes of presence, oldest first
+    #[cfg_attr(feature = "serde", serde(skip))]
+    changes: Vec<PresenceChange>,
+    /// Role in the event
+    #[cfg_attr(feature = "serde", serde(default))]
+    role: Role,
+    /// Presence saved on the intra, when the student was fetched or saved
+    #[cfg_attr(feature = "serde", serde(default))]
+    saved_presence: Presence,
 }
 
 impl Student {
+    /// Start building a student, without fetching it from the intra
+    pub fn builder() -> StudentBuilder {
+        StudentBuilder::new()
+    }
+
     /// Get student's email address
     pub fn get_login(&self) -> &str {
         &self.login
@@ -82,15 +283,367 @@ impl Student {
         &self.name
     }
 
+    /// Get student's first name, from the login
+    ///
+    /// Logins follow the `first.last@domain` format,
+    /// `None` is returned if the login does not follow it.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use epitok::student::Student;
+    ///
+    /// let student = Student::builder().login("first.last@epitech.eu").build();
+    /// assert_eq!(student.first_name(), Some("first"));
+    /// ```
+    pub fn first_name(&self) -> Option<&str> {
+        self.login_names().map(|(first, _)| first)
+    }
+
+    /// Get student's last name, from the login
+    ///
+    /// See `first_name` for the format of logins.
+    pub fn last_name(&self) -> Option<&str> {
+        self.login_names().map(|(_, last)| last)
+    }
+
+    /// Get domain of student's login, which tells the school of the student
+    ///
+    /// # Output format
+    /// `epitech.eu`, `epitech-digitalschool.fr`...
+    pub fn campus(&self) -> Option<&str> {
+        match self.login.split_once('@') {
+            Some((_, domain)) if !domain.is_empty() => Some(domain),
+            _ => None,
+        }
+    }
+
+    /// Split local part of the login in first and last name
+    fn login_names(&self) -> Option<(&str, &str)> {
+        let (local, _) = self.login.split_once('@')?;
+        match local.split_once('.') {
+            Some((first, last)) if !first.is_empty() && !last.is_empty() => Some((first, last)),
+            _ => None,
+        }
+    }
+
     /// Get student's presence
     pub fn get_presence(&self) -> &Presence {
         &self.presence
     }
 
     /// Set student's presence
+    ///
+    /// The change is recorded in the history of the student, unless the presence is the same.
     pub fn set_presence(&mut self, presence: Presence) {
+        if self.presence == presence {
+            return;
+        }
+
+        self.changes.push(PresenceChange {
+            from: self.presence,
+            to: presence,
+            timestamp: chrono::Local::now(),
+        });
         self.presence = presence
     }
+
+    /// Check if the presence has been modified since the student was fetched or saved
+    ///
+    /// Students built with `StudentBuilder` have no saved presence.
+    pub fn is_modified(&self) -> bool {
+        self.presence != self.saved_presence
+    }
+
+    /// Use current presence as the one saved on the intra
+    pub(crate) fn mark_saved(&mut self) {
+        self.saved_presence = self.presence;
+    }
+
+    /// Use a presence read from the intra as the one saved on the intra
+    pub(crate) fn set_saved_presence(&mut self, presence: Presence) {
+        self.saved_presence = presence;
+    }
+
+    /// Get changes of presence of the student, oldest first
+    pub fn changes(&self) -> &[PresenceChange] {
+        &self.changes
+    }
+
+    /// Undo the last change of presence
+    ///
+    /// The presence before the change is restored, and the change is returned.
+    /// If there are no changes, `None` is returned.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use epitok::student::{Presence, Student};
+    ///
+    /// let mut student = Student::builder().login("first.last@epitech.eu").build();
+    /// student.set_presence(Presence::Present);
+    /// student.undo_last_change();
+    /// assert_eq!(*student.get_presence(), Presence::None);
+    /// ```
+    pub fn undo_last_change(&mut self) -> Option<PresenceChange> {
+        let change = self.changes.pop()?;
+        self.presence = change.from;
+        Some(change)
+    }
+
+    /// Get student's promotion, if known
+    pub fn get_promo(&self) -> Option<&str> {
+        self.promo.as_deref()
+    }
+
+    /// Get student's group, if known
+    pub fn get_group(&self) -> Option<&str> {
+        self.group.as_deref()
+    }
+
+    /// Get student's semester, if known
+    pub fn get_semester(&self) -> Option<i32> {
+        self.semester
+    }
+
+    /// Get role in the event
+    pub fn get_role(&self) -> Role {
+        self.role
+    }
+
+    /// Check if this is a staff member (assistant or teacher) instead of a student
+    pub fn is_staff(&self) -> bool {
+        self.role.is_staff()
+    }
+}
+
+#[derive(Debug, Default)]
+/// # Student builder
+///
+/// Build a student from local data, such as test fixtures
+///
+/// # Example
+///
+/// ```
+/// use epitok::student::{Presence, StudentBuilder};
+///
+/// let student = StudentBuilder::new()
+///     .login("first.last@epitech.eu")
+///     .name("First Last")
+///     .presence(Presence::Present)
+///     .build();
+///
+/// assert_eq!(student.get_login(), "first.last@epitech.eu");
+/// assert_eq!(student.get_name(), "First Last");
+/// assert_eq!(*student.get_presence(), Presence::Present);
+/// ```
+pub struct StudentBuilder {
+    login: String,
+    name: String,
+    presence: Option<Presence>,
+    promo: Option<String>,
+    group: Option<String>,
+    semester: Option<i32>,
+    role: Role,
+}
+
+impl StudentBuilder {
+    /// Create a builder for a student without presence
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set email address
+    pub fn login(mut self, login: &str) -> Self {
+        self.login = login.to_string();
+        self
+    }
+
+    /// Set name
+    pub fn name(mut self, name: &str) -> Self {
+        self.name = name.to_string();
+        self
+    }
+
+    /// Set presence status
+    pub fn presence(mut self, presence: Presence) -> Self {
+        self.presence = Some(presence);
+        self
+    }
+
+    /// Set promotion
+    pub fn promo(mut self, promo: &str) -> Self {
+        self.promo = Some(promo.to_string());
+        self
+    }
+
+    /// Set group
+    pub fn group(mut self, group: &str) -> Self {
+        self.group = Some(group.to_string());
+        self
+    }
+
+    /// Set semester
+    pub fn semester(mut self, semester: i32) -> Self {
+        self.semester = Some(semester);
+        self
+    }
+
+    /// Set role, `Role::Student` by default
+    pub fn role(mut self, role: Role) -> Self {
+        self.role = role;
+        self
+    }
+
+    /// Build the student
+    pub fn build(self) -> Student {
+        Student {
+            login: self.login,
+            name: self.name,
+            presence: self.presence.unwrap_or(Presence::None),
+            changes: Vec::new(),
+            promo: self.promo,
+            group: self.group,
+            semester: self.semester,
+            role: self.role,
+            saved_presence: Presence::None,
+        }
+    }
+}
+
+/// Students are equal if they have the same login, whatever their presence
+///
+/// # Example
+///
+/// ```
+/// use epitok::student::{Presence, Student};
+/// use std::collections::HashSet;
+///
+/// let present = Student::builder()
+///     .login("first.last@epitech.eu")
+///     .presence(Presence::Present)
+///     .build();
+/// let missing = Student::builder()
+///     .login("first.last@epitech.eu")
+///     .presence(Presence::Missing)
+///     .build();
+///
+/// let set: HashSet<Student> = vec![present, missing].into_iter().collect();
+/// assert_eq!(set.len(), 1);
+/// ```
+impl PartialEq for Student {
+    fn eq(&self, other: &Self) -> bool {
+        self.login == other.login
+    }
+}
+
+impl Eq for Student {}
+
+impl Hash for Student {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.login.hash(state);
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+/// # Presence summary
+///
+/// Number of students for each presence
+///
+/// # Example
+///
+/// ```
+/// use epitok::student::summarize;
+///
+/// let summary = summarize(&[]);
+/// assert_eq!(summary.total, 0);
+/// assert_eq!(summary.attendance_rate(), 0.0);
+/// ```
+pub struct PresenceSummary {
+    /// Number of students, staff members excluded
+    pub total: usize,
+    /// Students set as present
+    pub present: usize,
+    /// Students set as missing
+    pub missing: usize,
+    /// Students set as N/A
+    pub not_applicable: usize,
+    /// Students set as late
+    pub late: usize,
+    /// Students set as excused
+    pub excused: usize,
+    /// Students whose presence failed to be saved
+    pub failed: usize,
+    /// Students without a presence
+    pub none: usize,
+}
+
+impl PresenceSummary {
+    /// Get fraction of students set as present or late, between `0.0` and `1.0`
+    ///
+    /// If there are no students, `0.0` will be returned.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use epitok::student::{summarize, Presence, Role, Student};
+    ///
+    /// let students: Vec<Student> = ["first.one", "second.one"]
+    ///     .iter()
+    ///     .map(|login| Student::builder().login(login).presence(Presence::Late).build())
+    ///     .collect();
+    ///
+    /// assert_eq!(summarize(&students).attendance_rate(), 1.0);
+    ///
+    /// // staff members are not counted
+    /// let mut students = students;
+    /// students.push(
+    ///     Student::builder()
+    ///         .login("assi.stant@epitech.eu")
+    ///         .role(Role::Assistant)
+    ///         .build(),
+    /// );
+    /// assert_eq!(summarize(&students).total, 2);
+    /// ```
+    pub fn attendance_rate(&self) -> f64 {
+        if self.total == 0 {
+            return 0.0;
+        }
+
+        (self.present + self.late) as f64 / self.total as f64
+    }
+}
+
+impl From<&Event> for PresenceSummary {
+    fn from(event: &Event) -> Self {
+        summarize(&event.students)
+    }
+}
+
+/// Count students of each presence, in a single pass
+///
+/// Staff members (assistants and teachers) are not counted.
+///
+/// # Arguments
+///
+/// * `students` - List of students
+pub fn summarize(students: &[Student]) -> PresenceSummary {
+    let mut summary = PresenceSummary::default();
+
+    for student in students.iter().filter(|student| !student.is_staff()) {
+        summary.total += 1;
+        match student.get_presence() {
+            Presence::None => summary.none += 1,
+            Presence::Present => summary.present += 1,
+            Presence::Missing => summary.missing += 1,
+            Presence::NotApplicable => summary.not_applicable += 1,
+            Presence::Late => summary.late += 1,
+            Presence::Excused => summary.excused += 1,
+            Presence::Failed => summary.failed += 1,
+        }
+    }
+
+    summary
 }
 
 #[derive(Debug)]
@@ -100,69 +653,443 @@ pub enum Error {
     Login,
     /// Student does not have a name
     Name,
+    /// Value is not a known presence
+    Presence(String),
 }
 
 impl error::Error for Error {}
 
 impl fmt::Display for Error {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        let message = match *self {
-            Error::Login => "Student does not have an epitech login",
-            Error::Name => "Student does not have a name",
+        let message = match self {
+            Error::Login => "Student does not have an epitech login".into(),
+            Error::Name => "Student does not have a name".into(),
+            Error::Presence(value) => format!("Unknown presence: {}", value),
         };
         write!(f, "{}", message)
     }
 }
 
 /// Get list of students from an event
+///
+/// On failure the list is left untouched.
+///
+/// # Example
+///
+/// ```no_run
+/// use epitok::student::{fetch_students, Student};
+///
+/// # #[async_std::main]
+/// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let autologin = "https://intra.epitech.eu/auth-abcdefghijklmnopqrstuvwxyz1234567890abcd";
+/// let code = "/module/2020/B-CPE-110/PAR-1-1/acti-123456/event-654321";
+/// let mut students: Vec<Student> = Vec::new();
+///
+/// fetch_students(&mut students, autologin, code).await?;
+/// # Ok(())
+/// # }
+/// ```
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(skip_all, fields(event = %event_code), err)
+)]
 pub async fn fetch_students(
     list: &mut Vec<Student>,
     autologin: &str,
     event_code: &str,
 ) -> Result<usize, Box<dyn error::Error>> {
+    fetch_students_with_client(intra::IntraClient::shared(), list, autologin, event_code).await
+}
+
+/// Fetch students registered to an event, with a client instead of the shared one
+///
+/// See `fetch_students`.
+///
+/// # Arguments
+///
+/// * `client` - Client requests are made with
+/// * `list` - Vector of students to write to
+/// * `autologin` - User autologin link
+/// * `event_code` - URL code of event
+///
+/// # Example
+///
+/// ```no_run
+/// use epitok::intra::{IntraClient, IntraConfig};
+/// use epitok::student::fetch_students_with_client;
+///
+/// # #[async_std::main]
+/// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let autologin = "https://intra.epitech.eu/auth-abcdefghijklmnopqrstuvwxyz1234567890abcd";
+/// let code = "/module/2020/B-CPE-110/PAR-1-1/acti-123456/event-654321";
+/// let client = IntraClient::with_config(IntraConfig {
+///     max_retries: 0,
+///     ..IntraConfig::default()
+/// });
+///
+/// let mut students = Vec::new();
+/// fetch_students_with_client(&client, &mut students, autologin, code).await?;
+/// # Ok(())
+/// # }
+/// ```
+pub async fn fetch_students_with_client(
+    client: &intra::IntraClient,
+    list: &mut Vec<Student>,
+    autologin: &str,
+    event_code: &str,
+) -> Result<usize, Box<dyn error::Error>> {
+    *list = students_for_with_client(client, autologin, event_code).await?;
+
+    Ok(list.len())
+}
+
+/// Get students registered to an event
+///
+/// # Arguments
+///
+/// * `autologin` - User autologin link
+/// * `event_code` - URL code of event
+pub async fn students_for(
+    autologin: &str,
+    event_code: &str,
+) -> Result<Vec<Student>, Box<dyn error::Error>> {
+    students_for_with_client(intra::IntraClient::shared(), autologin, event_code).await
+}
+
+/// Get students registered to an event, with a client instead of the shared one
+///
+/// # Arguments
+///
+/// * `client` - Client requests are made with
+/// * `autologin` - User autologin link
+/// * `event_code` - URL code of event
+pub async fn students_for_with_client(
+    client: &intra::IntraClient,
+    autologin: &str,
+    event_code: &str,
+) -> Result<Vec<Student>, Box<dyn error::Error>> {
     let url = format!("{}{}/registered?format=json", autologin, event_code);
 
-    let json = match intra::get_array_obj(&url).await {
+    let json = match client.get_array_obj(&url).await {
         Ok(json) => json,
         Err(e) => {
             return match e {
-                intra::Error::Empty => Ok(0), // No students have signed up for this event
-                _ => Err(e.into()),           // Return the intra error
+                intra::Error::Empty => Ok(Vec::new()), // No students have signed up for this event
+                _ => Err(e.into()),                    // Return the intra error
             };
         }
     };
 
-    // clear vector if it's not empty
-    if !list.is_empty() {
-        list.clear();
+    Ok(parse_students(&json)?)
+}
+
+/// Get students registered to a module instance
+///
+/// Students don't have a presence status.
+///
+/// # Arguments
+///
+/// * `autologin` - User autologin link
+/// * `year` - Scolar year of module
+/// * `module` - Code of module, `X-XXX-000`
+/// * `instance` - Code of instance, `XXX-0-0`
+pub async fn fetch_module_roster(
+    autologin: &str,
+    year: &str,
+    module: &str,
+    instance: &str,
+) -> Result<Vec<Student>, Box<dyn error::Error>> {
+    let url = format!(
+        "{}/module/{}/{}/{}/registered?format=json",
+        autologin, year, module, instance
+    );
+
+    let json = match intra::get_array_obj(&url).await {
+        Ok(json) => json,
+        Err(intra::Error::Empty) => return Ok(Vec::new()), // No students have registered to this module
+        Err(e) => return Err(e.into()),
+    };
+
+    Ok(parse_students(&json)?)
+}
+
+/// Build students from a list of registered students
+///
+/// Staff members are kept, with their role: see `Student::is_staff`.
+///
+/// # Example
+///
+/// ```
+/// use epitok::student::parse_students;
+///
+/// let json = vec![serde_json::json!({ "login": "first.last@epitech.eu", "title": "First Last" })];
+/// let students = parse_students(&json).unwrap();
+/// assert_eq!(students[0].get_name(), "First Last");
+/// ```
+pub fn parse_students(json: &[serde_json::Value]) -> Result<Vec<Student>, Error> {
+    let mut students = Vec::with_capacity(json.len());
+
+    for student in json {
+        students.push(parse_student(student)?);
     }
 
-    let mut number_students = 0;
+    Ok(students)
+}
 
-    for student in &json {
-        let login = match student["login"].as_str() {
-            Some(login) => login.to_string(),
-            None => return Err(Error::Login.into()),
-        };
+/// Get students registered to an event, by login
+///
+/// Same request as `fetch_students`, to look up students by login without searching a list.
+///
+/// # Arguments
+///
+/// * `autologin` - User autologin link
+/// * `event_code` - URL code of event
+pub async fn fetch_students_map(
+    autologin: &str,
+    event_code: &str,
+) -> Result<HashMap<String, Student>, Box<dyn error::Error>> {
+    let students = students_for(autologin, event_code).await?;
 
-        let name = match student["title"].as_str() {
-            Some(name) => name.to_string(),
-            None => return Err(Error::Name.into()),
-        };
+    Ok(students
+        .into_iter()
+        .map(|student| (student.login.clone(), student))
+        .collect())
+}
 
-        let presence = match student["present"].as_str() {
-            Some(presence) => Presence::from(presence),
-            None => Presence::None,
-        };
+/// Get students registered to an event, by login
+///
+/// Same as `fetch_students_map`.
+///
+/// # Arguments
+///
+/// * `autologin` - User autologin link
+/// * `event_code` - URL code of event
+pub async fn fetch_students_as_map(
+    autologin: &str,
+    event_code: &str,
+) -> Result<HashMap<String, Student>, Box<dyn error::Error>> {
+    fetch_students_map(autologin, event_code).await
+}
 
-        list.push(Student {
-            login,
-            name,
-            presence,
-        });
+/// Build a student from an entry of a list of registered students
+pub(crate) fn parse_student(student: &serde_json::Value) -> Result<Student, Error> {
+    let login = match student["login"].as_str() {
+        Some(login) => login.to_string(),
+        None => return Err(Error::Login),
+    };
 
-        number_students += 1;
+    let name = match student["title"].as_str() {
+        Some(name) => intra::decode_entities(name),
+        None => return Err(Error::Name),
+    };
+
+    let presence = match student["present"].as_str() {
+        Some(presence) => Presence::from(presence),
+        None => Presence::None,
+    };
+
+    Ok(Student {
+        login,
+        name,
+        presence,
+        changes: Vec::new(),
+        promo: parse_text(&student["promo"]),
+        group: parse_group(&student["group"]),
+        semester: parse_semester(&student["semester"]),
+        role: Role::from(student["type"].as_str().unwrap_or_default()),
+        saved_presence: presence,
+    })
+}
+
+/// Read a text sent either as a string or as a number, empty texts are ignored
+fn parse_text(json: &serde_json::Value) -> Option<String> {
+    intra::parse_value::<String>(json).filter(|text| !text.is_empty())
+}
+
+/// Read group of student, sent either as its name or as an object
+fn parse_group(json: &serde_json::Value) -> Option<String> {
+    match json {
+        serde_json::Value::Object(group) => group
+            .get("title")
+            .or_else(|| group.get("code"))
+            .and_then(parse_text),
+        _ => parse_text(json),
     }
+}
 
-    Ok(number_students)
+/// Read semester of student, sent as a number or as a string (`3` or `B3`)
+fn parse_semester(json: &serde_json::Value) -> Option<i32> {
+    match json {
+        serde_json::Value::String(text) => text
+            .trim_start_matches(|c: char| c.is_ascii_alphabetic())
+            .parse()
+            .ok(),
+        _ => intra::parse_value(json),
+    }
+}
+
+/// Keep students of a promotion
+///
+/// # Arguments
+///
+/// * `students` - List of students
+/// * `promo` - Promotion to keep, such as `2024`
+pub fn filter_students_by_promo<'a>(students: &'a [Student], promo: &str) -> Vec<&'a Student> {
+    students
+        .iter()
+        .filter(|student| student.get_promo() == Some(promo))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::intra::{IntraConfig, MockBackend};
+    use crate::testing::{block_on, serve_once, AUTOLOGIN, CODE};
+
+    const ALL: [Presence; 7] = [
+        Presence::None,
+        Presence::Present,
+        Presence::Missing,
+        Presence::NotApplicable,
+        Presence::Late,
+        Presence::Excused,
+        Presence::Failed,
+    ];
+
+    /// Presences a predicate holds for
+    fn holding(predicate: fn(&Presence) -> bool) -> Vec<Presence> {
+        ALL.iter().copied().filter(predicate).collect()
+    }
+
+    #[test]
+    fn presence_from_intra_value() {
+        // a presence can be read back from its intra value
+        for presence in &ALL {
+            assert_eq!(Presence::from(&presence.to_string()), *presence);
+            assert_eq!(presence.to_string().parse::<Presence>().unwrap(), *presence);
+        }
+
+        assert_eq!("null".parse::<Presence>().unwrap(), Presence::None);
+        assert!("maybe".parse::<Presence>().is_err());
+        assert_eq!(Presence::from("maybe"), Presence::Failed);
+    }
+
+    #[test]
+    fn presence_uploaded_values() {
+        assert_eq!(
+            Presence::Late.intra_value(),
+            Presence::Present.intra_value()
+        );
+        assert_eq!(Presence::Excused.intra_value(), "N/A");
+        assert_eq!(Presence::Late.to_string(), "late");
+    }
+
+    #[test]
+    fn presence_predicates() {
+        assert_eq!(holding(Presence::is_set).len(), ALL.len() - 1);
+        assert!(!Presence::None.is_set());
+        assert_eq!(
+            holding(Presence::is_present),
+            vec![Presence::Present, Presence::Late]
+        );
+        assert_eq!(holding(Presence::is_absent), vec![Presence::Missing]);
+        assert_eq!(
+            holding(Presence::is_not_applicable),
+            vec![Presence::NotApplicable]
+        );
+        assert_eq!(holding(Presence::is_failed), vec![Presence::Failed]);
+    }
+
+    #[test]
+    fn names_from_login() {
+        let student = Student::builder().login("first.last@epitech.eu").build();
+        assert_eq!(student.first_name(), Some("first"));
+        assert_eq!(student.last_name(), Some("last"));
+        assert_eq!(student.campus(), Some("epitech.eu"));
+
+        let student = Student::builder().login("firstlast").build();
+        assert_eq!(student.first_name(), None);
+        assert_eq!(student.campus(), None);
+    }
+
+    #[test]
+    fn undo_changes_in_order() {
+        let mut student = Student::builder().login("first.last@epitech.eu").build();
+        student.set_presence(Presence::Present);
+        student.set_presence(Presence::Missing);
+        assert_eq!(student.changes().len(), 2);
+
+        let change = student.undo_last_change().unwrap();
+        assert_eq!(change.from, Presence::Present);
+        assert_eq!(change.to, Presence::Missing);
+        assert_eq!(*student.get_presence(), Presence::Present);
+
+        student.undo_last_change();
+        assert_eq!(*student.get_presence(), Presence::None);
+        assert!(student.undo_last_change().is_none());
+    }
+
+    #[test]
+    fn parse_students_with_staff() {
+        let json = serde_json::json!([
+            { "login": "first.last@epitech.eu", "title": "First Last", "present": "present" },
+            { "login": "assi.stant@epitech.eu", "title": "Assi Stant", "present": "N/A", "type": "assistant" },
+            { "login": "sean.obrien@epitech.eu", "title": "Se&aacute;n O&#039;Brien" }
+        ]);
+
+        let students = parse_students(json.as_array().unwrap()).unwrap();
+        assert_eq!(students[2].get_name(), "Seán O'Brien");
+        assert_eq!(students[0].get_role(), Role::Student);
+        assert_eq!(students[1].get_role(), Role::Assistant);
+        assert!(students[1].is_staff());
+    }
+
+    #[test]
+    fn fetch_students_with_mock() {
+        let backend = MockBackend::new().with_response(
+            &format!("{}{}/registered?format=json", AUTOLOGIN, CODE),
+            r#"[{"login": "student.one@epitech.eu", "title": "Student One", "present": "present"}]"#,
+        );
+        let client = intra::IntraClient::with_backend(IntraConfig::default(), backend);
+
+        let mut students = Vec::new();
+        let fetched = futures::executor::block_on(fetch_students_with_client(
+            &client,
+            &mut students,
+            AUTOLOGIN,
+            CODE,
+        ))
+        .unwrap();
+        assert_eq!(fetched, 1);
+        assert_eq!(students[0].get_login(), "student.one@epitech.eu");
+    }
+
+    #[test]
+    fn fetch_students_untouched_on_server_error() {
+        let (url, server) = serve_once("500 Internal Server Error", "");
+        let autologin = format!("{}/auth-abcdefghijklmnopqrstuvwxyz1234567890abcd", url);
+        // fail on the first reply, without retrying
+        let client = intra::IntraClient::with_config(IntraConfig {
+            max_retries: 0,
+            ..IntraConfig::default()
+        });
+
+        let mut students = vec![Student::builder().login("first.last@epitech.eu").build()];
+        let error = block_on(fetch_students_with_client(
+            &client,
+            &mut students,
+            &autologin,
+            CODE,
+        ))
+        .unwrap_err();
+        server.join().unwrap();
+
+        assert!(matches!(
+            error.downcast_ref::<intra::Error>(),
+            Some(intra::Error::ServerError(500))
+        ));
+        assert_eq!(students.len(), 1);
+        assert_eq!(students[0].get_login(), "first.last@epitech.eu");
+    }
 }
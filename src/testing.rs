@@ -0,0 +1,118 @@
+//! Fixtures shared by the tests of the modules
+
+use crate::event::{parse_planning, Event, ListOptions};
+use crate::student::parse_students;
+use std::io::{Read, Write};
+use std::net::TcpListener;
+use std::thread::JoinHandle;
+
+/// Autologin link of the tests
+pub(crate) const AUTOLOGIN: &str =
+    "https://intra.epitech.eu/auth-abcdefghijklmnopqrstuvwxyz1234567890abcd";
+
+/// Code of the event of `planning_entry`
+pub(crate) const CODE: &str = "/module/2020/B-CPE-110/PAR-1-1/acti-123456/event-654321";
+
+/// Entry of the planning, from 09:00 to 11:00 on 2020-07-01
+pub(crate) fn planning_entry() -> serde_json::Value {
+    serde_json::json!({
+        "scolaryear": "2020", "codemodule": "B-CPE-110", "codeinstance": "PAR-1-1",
+        "codeacti": "acti-123456", "codeevent": "event-654321",
+        "acti_title": "Bootstrap", "titlemodule": "Unix & C Lab Seminar",
+        "start": "2020-07-01 09:00:00", "end": "2020-07-01 11:00:00", "is_rdv": "0"
+    })
+}
+
+/// Entry of the planning with other values for some of its keys
+pub(crate) fn planning_entry_with(values: serde_json::Value) -> serde_json::Value {
+    let mut entry = planning_entry();
+    if let (Some(entry), Some(values)) = (entry.as_object_mut(), values.as_object()) {
+        entry.extend(values.clone());
+    }
+    entry
+}
+
+/// Event of a planning entry
+pub(crate) fn event_from(entry: serde_json::Value) -> Event {
+    parse_planning(&[entry], &ListOptions::new())
+        .events
+        .remove(0)
+}
+
+/// Event of `planning_entry`
+pub(crate) fn event() -> Event {
+    event_from(planning_entry())
+}
+
+/// Event with students as fetched from the intra, with their login and intra presence
+pub(crate) fn event_with(students: &[(&str, &str)]) -> Event {
+    let json: Vec<serde_json::Value> = students
+        .iter()
+        .map(|(login, present)| {
+            serde_json::json!({ "login": login, "title": login, "present": present })
+        })
+        .collect();
+
+    let mut event = event();
+    event.students = parse_students(&json).unwrap();
+    event
+}
+
+/// Serve a single HTTP reply on a local port
+///
+/// The URL of the server and the handle of its thread are returned,
+/// joining the thread gives the request that was received.
+pub(crate) fn serve_once(status: &str, body: &str) -> (String, JoinHandle<String>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    let reply = format!(
+        "HTTP/1.1 {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        body.len(),
+        body
+    );
+
+    let server = std::thread::spawn(move || {
+        let (mut stream, _) = listener.accept().unwrap();
+        let mut request = Vec::new();
+        let mut buffer = [0; 1024];
+        // read the whole request, closing with unread data resets the connection
+        while !is_complete(&request) {
+            let n = stream.read(&mut buffer).unwrap();
+            if n == 0 {
+                break;
+            }
+            request.extend_from_slice(&buffer[..n]);
+        }
+        stream.write_all(reply.as_bytes()).unwrap();
+        String::from_utf8_lossy(&request).to_string()
+    });
+
+    (url, server)
+}
+
+/// Check if a request has been received with its headers and its body
+fn is_complete(request: &[u8]) -> bool {
+    let request = String::from_utf8_lossy(request);
+    let (headers, body) = match request.split_once("\r\n\r\n") {
+        Some(parts) => parts,
+        None => return false,
+    };
+
+    let length = headers
+        .lines()
+        .filter_map(|line| line.split_once(':'))
+        .find(|(name, _)| name.eq_ignore_ascii_case("content-length"))
+        .and_then(|(_, value)| value.trim().parse().ok())
+        .unwrap_or(0);
+    body.len() >= length
+}
+
+/// Run a future on a runtime able to make HTTP requests
+pub(crate) fn block_on<F: std::future::Future>(future: F) -> F::Output {
+    tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .unwrap()
+        .block_on(future)
+}